@@ -17,7 +17,7 @@
 use sp_keyring::AccountKeyring;
 use substrate_subxt::{
     balances::{
-        BalancesEventsDecoder,
+        TransferCall,
         TransferCallExt,
         TransferEvent,
     },
@@ -25,7 +25,6 @@ use substrate_subxt::{
     ClientBuilder,
     DefaultNodeRuntime,
     EventSubscription,
-    EventsDecoder,
     PairSigner,
 };
 
@@ -38,8 +37,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let client = ClientBuilder::<DefaultNodeRuntime>::new().build().await?;
     let sub = client.subscribe_events().await?;
-    let mut decoder = EventsDecoder::<DefaultNodeRuntime>::new(client.metadata().clone());
-    decoder.with_balances();
+    let decoder = client.events_decoder::<TransferCall<DefaultNodeRuntime>>();
     let mut sub = EventSubscription::<DefaultNodeRuntime>::new(sub, decoder);
     sub.filter_event::<TransferEvent<_>>();
     client.transfer(&signer, &dest, 10_000).await?;
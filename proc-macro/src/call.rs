@@ -46,6 +46,8 @@ pub fn call(s: Structure) -> TokenStream {
     let call_trait = format_ident!("{}CallExt", call_name.to_camel_case());
     let call = format_ident!("{}", call_name);
     let call_and_watch = format_ident!("{}_and_watch", call_name);
+    let call_with_tip = format_ident!("{}_with_tip", call_name);
+    let call_and_finalize = format_ident!("{}_and_finalize", call_name);
 
     quote! {
         impl#generics #subxt::Call<T> for #ident<#(#params),*> {
@@ -73,6 +75,21 @@ pub fn call(s: Structure) -> TokenStream {
                 signer: &'a (dyn #subxt::Signer<T> + Send + Sync),
                 #args
             ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<#subxt::ExtrinsicSuccess<T>, #subxt::Error>> + Send + 'a>>;
+
+            /// Create and submit an extrinsic paying a tip to the block author.
+            fn #call_with_tip<'a>(
+                &'a self,
+                signer: &'a (dyn #subxt::Signer<T> + Send + Sync),
+                #args
+                tip: #subxt::extrinsic::Tip<T>,
+            ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<T::Hash, #subxt::Error>> + Send + 'a>>;
+
+            /// Create and submit an extrinsic and wait until the block including it is finalized.
+            fn #call_and_finalize<'a>(
+                &'a self,
+                signer: &'a (dyn #subxt::Signer<T> + Send + Sync),
+                #args
+            ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<#subxt::ExtrinsicSuccess<T>, #subxt::Error>> + Send + 'a>>;
         }
 
         impl<T: #subxt::Runtime + #module> #call_trait<T> for #subxt::Client<T>
@@ -96,6 +113,41 @@ pub fn call(s: Structure) -> TokenStream {
                 let #marker = core::marker::PhantomData::<T>;
                 Box::pin(self.watch(#build_struct, signer))
             }
+
+            fn #call_with_tip<'a>(
+                &'a self,
+                signer: &'a (dyn #subxt::Signer<T> + Send + Sync),
+                #args
+                tip: #subxt::extrinsic::Tip<T>,
+            ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<T::Hash, #subxt::Error>> + Send + 'a>> {
+                let #marker = core::marker::PhantomData::<T>;
+                Box::pin(self.submit_with_tip(#build_struct, signer, tip))
+            }
+
+            fn #call_and_finalize<'a>(
+                &'a self,
+                signer: &'a (dyn #subxt::Signer<T> + Send + Sync),
+                #args
+            ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<#subxt::ExtrinsicSuccess<T>, #subxt::Error>> + Send + 'a>> {
+                let #marker = core::marker::PhantomData::<T>;
+                Box::pin(self.finalize(#build_struct, signer))
+            }
+        }
+    }
+}
+
+pub fn decode_call(s: Structure) -> TokenStream {
+    let subxt = utils::use_crate("substrate-subxt");
+    let ident = &s.ast().ident;
+    let generics = &s.ast().generics;
+    let params = utils::type_params(generics);
+    let name = utils::ident_to_name(ident, "Call");
+    let call_name = name.strip_prefix("Owned").unwrap_or(&name).to_snake_case();
+
+    quote! {
+        impl#generics #subxt::DecodeCall<T> for #ident<#(#params),*> {
+            const MODULE: &'static str = MODULE;
+            const FUNCTION: &'static str = #call_name;
         }
     }
 }
@@ -104,6 +156,28 @@ pub fn call(s: Structure) -> TokenStream {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_owned_transfer_call() {
+        let input = quote! {
+            #[derive(Debug, Decode, DecodeCall, Eq, PartialEq)]
+            pub struct OwnedTransferCall<T: Balances> {
+                pub to: <T as System>::Address,
+                #[codec(compact)]
+                pub amount: T::Balance,
+            }
+        };
+        let expected = quote! {
+            impl<T: Balances> substrate_subxt::DecodeCall<T> for OwnedTransferCall<T> {
+                const MODULE: &'static str = MODULE;
+                const FUNCTION: &'static str = "transfer";
+            }
+        };
+        let derive_input = syn::parse2(input).unwrap();
+        let s = Structure::new(&derive_input);
+        let result = decode_call(s);
+        utils::assert_proc_macro(result, expected);
+    }
+
     #[test]
     fn test_transfer_call() {
         let input = quote! {
@@ -142,6 +216,23 @@ mod tests {
                     to: &'a <T as System>::Address,
                     amount: T::Balance,
                 ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<substrate_subxt::ExtrinsicSuccess<T>, substrate_subxt::Error>> + Send + 'a>>;
+
+                /// Create and submit an extrinsic paying a tip to the block author.
+                fn transfer_with_tip<'a>(
+                    &'a self,
+                    signer: &'a (dyn substrate_subxt::Signer<T> + Send + Sync),
+                    to: &'a <T as System>::Address,
+                    amount: T::Balance,
+                    tip: substrate_subxt::extrinsic::Tip<T>,
+                ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<T::Hash, substrate_subxt::Error>> + Send + 'a>>;
+
+                /// Create and submit an extrinsic and wait until the block including it is finalized.
+                fn transfer_and_finalize<'a>(
+                    &'a self,
+                    signer: &'a (dyn substrate_subxt::Signer<T> + Send + Sync),
+                    to: &'a <T as System>::Address,
+                    amount: T::Balance,
+                ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<substrate_subxt::ExtrinsicSuccess<T>, substrate_subxt::Error>> + Send + 'a>>;
             }
 
             impl<T: substrate_subxt::Runtime + Balances> TransferCallExt<T> for substrate_subxt::Client<T>
@@ -167,6 +258,27 @@ mod tests {
                     let _ = core::marker::PhantomData::<T>;
                     Box::pin(self.watch(TransferCall { to, amount, }, signer))
                 }
+
+                fn transfer_with_tip<'a>(
+                    &'a self,
+                    signer: &'a (dyn substrate_subxt::Signer<T> + Send + Sync),
+                    to: &'a <T as System>::Address,
+                    amount: T::Balance,
+                    tip: substrate_subxt::extrinsic::Tip<T>,
+                ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<T::Hash, substrate_subxt::Error>> + Send + 'a>> {
+                    let _ = core::marker::PhantomData::<T>;
+                    Box::pin(self.submit_with_tip(TransferCall { to, amount, }, signer, tip))
+                }
+
+                fn transfer_and_finalize<'a>(
+                    &'a self,
+                    signer: &'a (dyn substrate_subxt::Signer<T> + Send + Sync),
+                    to: &'a <T as System>::Address,
+                    amount: T::Balance,
+                ) -> core::pin::Pin<Box<dyn core::future::Future<Output = Result<substrate_subxt::ExtrinsicSuccess<T>, substrate_subxt::Error>> + Send + 'a>> {
+                    let _ = core::marker::PhantomData::<T>;
+                    Box::pin(self.finalize(TransferCall { to, amount, }, signer))
+                }
             }
         };
         let derive_input = syn::parse2(input).unwrap();
@@ -17,6 +17,7 @@
 extern crate proc_macro;
 
 mod call;
+mod constant;
 mod event;
 mod module;
 mod store;
@@ -105,7 +106,7 @@ decl_derive!(
     ///```
     ///
     /// Implements [substrate_subxt::Call](../substrate_subxt/trait.Call.html) and adds an extension trait that
-    /// provides two methods named as your struct.
+    /// provides four methods named as your struct.
     ///
     /// Example:
     /// ```rust,ignore
@@ -127,18 +128,49 @@ decl_derive!(
     /// ```
     ///
     /// When building a [Client](../substrate_subxt/struct.Client.html) parameterised to `MyRuntime`, you have access to
-    /// two new methods: `fun_stuff()` and `fun_stuff_and_watch()` by way of the derived `FunStuffExt`
-    /// trait. The `_and_watch` variant makes the call and waits for the result. The fields of the
-    /// input struct become arguments to the calls (ignoring the marker field).
+    /// four new methods: `fun_stuff()`, `fun_stuff_and_watch()`, `fun_stuff_with_tip()` and
+    /// `fun_stuff_and_finalize()` by way of the derived `FunStuffExt` trait. The `_and_watch` variant makes the
+    /// call and waits for the result. The `_with_tip` variant takes an additional `tip` argument which is paid to
+    /// the block author to gain priority in the transaction queue. The `_and_finalize` variant waits until the
+    /// block including the call is finalized. The fields of the input struct become arguments to the calls
+    /// (ignoring the marker field).
     ///
-    /// Under the hood the implementation calls [submit()](../substrate_subxt/struct.Client.html#method.submit) and
-    /// [watch()](../substrate_subxt/struct.Client.html#method.watch) respectively.
+    /// Under the hood the implementation calls [submit()](../substrate_subxt/struct.Client.html#method.submit),
+    /// [watch()](../substrate_subxt/struct.Client.html#method.watch),
+    /// [submit_with_tip()](../substrate_subxt/struct.Client.html#method.submit_with_tip) and
+    /// [finalize()](../substrate_subxt/struct.Client.html#method.finalize) respectively.
     #[proc_macro_error] call
 );
 fn call(s: Structure) -> TokenStream {
     call::call(s).into()
 }
 
+decl_derive!(
+    [DecodeCall] =>
+    /// Derive macro that implements [substrate_subxt::DecodeCall](../substrate_subxt/trait.DecodeCall.html)
+    /// for the owned counterpart of a call, so encoded calls can be decoded into it.
+    ///
+    /// The struct is named like the call with an `Owned` prefix, and holds owned versions of the
+    /// arguments of the call. It must implement `Decode`, e.g.:
+    ///
+    /// ```rust,ignore
+    /// #[derive(Decode, DecodeCall)]
+    /// pub struct OwnedFunStuffCall<T: MyTrait> {
+    ///     /// Runtime marker.
+    ///     pub _runtime: PhantomData<T>,
+    ///     /// The argument passed to the call.
+    ///     pub something: Vec<u8>,
+    /// }
+    /// ```
+    ///
+    /// An encoded `fun_stuff` call can then be decoded with
+    /// [find_call()](../substrate_subxt/struct.Client.html#method.find_call).
+    #[proc_macro_error] decode_call
+);
+fn decode_call(s: Structure) -> TokenStream {
+    call::decode_call(s).into()
+}
+
 decl_derive!([Event] => #[proc_macro_error] event);
 fn event(s: Structure) -> TokenStream {
     event::event(s).into()
@@ -149,6 +181,11 @@ fn store(s: Structure) -> TokenStream {
     store::store(s).into()
 }
 
+decl_derive!([Constant, attributes(constant)] => #[proc_macro_error] constant);
+fn constant(s: Structure) -> TokenStream {
+    constant::constant(s).into()
+}
+
 #[proc_macro]
 #[proc_macro_error]
 pub fn subxt_test(input: TokenStream) -> TokenStream {
@@ -16,6 +16,7 @@
 
 use jsonrpsee::{
     client::RequestError,
+    common::ErrorCode,
     transport::ws::WsNewDnsError,
 };
 use sp_core::crypto::SecretStringError;
@@ -23,6 +24,7 @@ use sp_runtime::{
     transaction_validity::TransactionValidityError,
     DispatchError,
 };
+use std::time::Duration;
 use thiserror::Error;
 
 use crate::metadata::{
@@ -57,17 +59,50 @@ pub enum Error {
     /// Metadata error.
     #[error("Metadata error: {0}")]
     Metadata(#[from] MetadataError),
-    /// Type size unavailable.
-    #[error("Type size unavailable while decoding event: {0:?}")]
+    /// Type definition unavailable.
+    #[error("Type definition unavailable while decoding event: {0:?}")]
     TypeSizeUnavailable(String),
     /// Runtime error.
     #[error("Runtime error: {0}")]
     Runtime(#[from] RuntimeError),
+    /// Watched extrinsic made no progress in time.
+    #[error("Timed out after {0:?} while watching extrinsic")]
+    Timeout(Duration),
+    /// Rpc request on a client built in offline mode.
+    #[error("Rpc request on an offline client")]
+    Offline,
     /// Other error.
     #[error("Other error: {0}")]
     Other(String),
 }
 
+/// Error code of the node when it rejects an invalid transaction.
+pub(crate) const POOL_INVALID_TX: i64 = 1010;
+/// Error code of the node when it can't tell whether a transaction is valid.
+const POOL_UNKNOWN_VALIDITY: i64 = 1011;
+/// Error code of the node when it rejects a temporarily banned transaction.
+const POOL_TEMPORARILY_BANNED: i64 = 1012;
+
+impl Error {
+    /// Returns `true` if the transaction pool rejected an extrinsic, so it never
+    /// entered the pool.
+    pub(crate) fn is_pool_rejection(&self) -> bool {
+        match self {
+            Error::Rpc(RequestError::Request(error)) => {
+                match error.code {
+                    ErrorCode::ServerError(code) => {
+                        code == POOL_INVALID_TX
+                            || code == POOL_UNKNOWN_VALIDITY
+                            || code == POOL_TEMPORARILY_BANNED
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
 impl From<SecretStringError> for Error {
     fn from(error: SecretStringError) -> Self {
         Error::SecretString(error)
@@ -125,7 +160,7 @@ impl RuntimeError {
                 let error = module.error(error)?;
                 Ok(Self::Module(ModuleError {
                     module: module.name().to_string(),
-                    error: error.to_string(),
+                    error: error.name().to_string(),
                 }))
             }
             DispatchError::BadOrigin => Ok(Self::BadOrigin),
@@ -139,6 +174,8 @@ impl RuntimeError {
 #[derive(Clone, Debug, Eq, Error, PartialEq)]
 #[error("{error} from {module}")]
 pub struct ModuleError {
+    /// Name of the module the error originates from.
     pub module: String,
+    /// Name of the error variant.
     pub error: String,
 }
@@ -20,22 +20,21 @@ use codec::{
     Decode,
     Encode,
     Input,
-    Output,
 };
-use frame_support::dispatch::DispatchInfo;
 use sp_runtime::{
     DispatchError,
     DispatchResult,
 };
 use std::{
-    collections::{
-        HashMap,
-        HashSet,
-    },
+    collections::HashSet,
     marker::{
         PhantomData,
         Send,
     },
+    sync::{
+        Arc,
+        RwLock,
+    },
 };
 
 use crate::{
@@ -46,8 +45,15 @@ use crate::{
     metadata::{
         EventArg,
         Metadata,
+        MetadataError,
+    },
+    types::{
+        TypeDef,
+        TypeRegistry,
+        Value,
     },
     Phase,
+    RuntimeState,
     System,
 };
 
@@ -62,25 +68,61 @@ pub struct RawEvent {
     pub data: Vec<u8>,
 }
 
+/// Event record of a block, the decoded counterpart of `frame_system::EventRecord`.
+#[derive(Debug)]
+pub struct EventRecord<T: System> {
+    /// Phase of the block's execution the event was emitted in.
+    pub phase: Phase,
+    /// Index of the event within the block.
+    pub index: u32,
+    /// Raw event or error.
+    pub raw: Raw,
+    /// Topics the event was deposited with.
+    pub topics: Vec<T::Hash>,
+}
+
+/// Metadata an `EventsDecoder` decodes events with.
+#[derive(Debug)]
+enum DecoderMetadata {
+    /// Fixed metadata, like the metadata of a previous runtime.
+    Fixed(Arc<Metadata>),
+    /// Metadata of the current runtime of a client, which is replaced on runtime
+    /// upgrades.
+    Current(Arc<RwLock<RuntimeState>>),
+}
+
 /// Events decoder.
 #[derive(Debug)]
 pub struct EventsDecoder<T> {
-    metadata: Metadata,
-    type_sizes: HashMap<String, usize>,
+    metadata: DecoderMetadata,
+    types: TypeRegistry,
     marker: PhantomData<fn() -> T>,
 }
 
 impl<T: System> EventsDecoder<T> {
     /// Creates a new `EventsDecoder`.
     pub fn new(metadata: Metadata) -> Self {
+        Self::new_shared(Arc::new(metadata))
+    }
+
+    pub(crate) fn new_shared(metadata: Arc<Metadata>) -> Self {
+        Self::with_source(DecoderMetadata::Fixed(metadata))
+    }
+
+    /// Creates a decoder which decodes events with the metadata of the current
+    /// runtime of a client, following its upgrades.
+    pub(crate) fn for_runtime(runtime: Arc<RwLock<RuntimeState>>) -> Self {
+        Self::with_source(DecoderMetadata::Current(runtime))
+    }
+
+    fn with_source(metadata: DecoderMetadata) -> Self {
         let mut decoder = Self {
             metadata,
-            type_sizes: HashMap::new(),
+            types: TypeRegistry::new(),
             marker: PhantomData,
         };
         // register default event arg type sizes for dynamic decoding of events
         decoder.register_type_size::<()>("PhantomData");
-        decoder.register_type_size::<DispatchInfo>("DispatchInfo");
         decoder.register_type_size::<bool>("bool");
         decoder.register_type_size::<u32>("ReferendumIndex");
         decoder.register_type_size::<[u8; 16]>("Kind");
@@ -101,31 +143,66 @@ impl<T: System> EventsDecoder<T> {
         decoder
     }
 
+    /// Creates a decoder which also knows the types of `types`, like the ones
+    /// registered to a client.
+    pub(crate) fn with_types(metadata: Arc<Metadata>, types: &TypeRegistry) -> Self {
+        let mut decoder = Self::new_shared(metadata);
+        decoder.types.extend(types);
+        decoder
+    }
+
+    /// Returns a decoder for other metadata, like the metadata of a previous runtime,
+    /// which knows the types registered to this decoder.
+    pub fn with_metadata(&self, metadata: Arc<Metadata>) -> Self {
+        Self {
+            metadata: DecoderMetadata::Fixed(metadata),
+            types: self.types.clone(),
+            marker: PhantomData,
+        }
+    }
+
     /// Register a type.
     pub fn register_type_size<U>(&mut self, name: &str) -> usize
     where
         U: Default + Codec + Send + 'static,
     {
         let size = U::default().encode().len();
-        self.type_sizes.insert(name.to_string(), size);
+        self.types.register(name, TypeDef::Opaque(size));
         size
     }
 
+    /// Register the definition of a type, for types which aren't of a fixed size.
+    pub fn register_type(&mut self, name: &str, def: TypeDef) {
+        self.types.register(name, def);
+    }
+
+    /// Returns the metadata events are decoded with.
+    fn metadata(&self) -> Arc<Metadata> {
+        match &self.metadata {
+            DecoderMetadata::Fixed(metadata) => metadata.clone(),
+            DecoderMetadata::Current(runtime) => {
+                runtime
+                    .read()
+                    .expect("runtime state lock poisoned")
+                    .metadata
+                    .clone()
+            }
+        }
+    }
+
     /// Check missing type sizes.
     pub fn check_missing_type_sizes(&self) {
         let mut missing = HashSet::new();
-        for module in self.metadata.modules_with_events() {
+        for module in self.metadata().modules_with_events() {
             for event in module.events() {
                 for arg in event.arguments() {
-                    for primitive in arg.primitives() {
-                        if !self.type_sizes.contains_key(&primitive) {
-                            missing.insert(format!(
-                                "{}::{}::{}",
-                                module.name(),
-                                event.name,
-                                primitive
-                            ));
-                        }
+                    for primitive in self.types.missing(&arg) {
+                        missing.insert(format!(
+                            "{}::{}::{}",
+                            module.name(),
+                            event.name,
+                            primitive
+                        ));
                     }
                 }
             }
@@ -139,60 +216,94 @@ impl<T: System> EventsDecoder<T> {
         }
     }
 
-    fn decode_raw_bytes<I: Input, W: Output>(
+    /// Decodes the arguments of an event, copying their raw bytes to `output`.
+    ///
+    /// Returns the values of the arguments, and the error of a failed dispatch if
+    /// one of them is a `DispatchError` or failed `DispatchResult`.
+    fn decode_args(
         &self,
         args: &[EventArg],
-        input: &mut I,
-        output: &mut W,
-    ) -> Result<(), Error> {
+        input: &mut &[u8],
+        output: &mut Vec<u8>,
+    ) -> Result<Option<DispatchError>, Error> {
+        let mut dispatch_error = None;
         for arg in args {
+            let start = *input;
+            self.types.skip(arg, input)?;
+            let bytes = &start[..start.len() - input.len()];
             match arg {
-                EventArg::Vec(arg) => {
-                    let len = <Compact<u32>>::decode(input)?;
-                    len.encode_to(output);
-                    for _ in 0..len.0 {
-                        self.decode_raw_bytes(&[*arg.clone()], input, output)?
-                    }
+                EventArg::Primitive(name) if name == "DispatchError" => {
+                    dispatch_error = Some(DispatchError::decode(&mut &bytes[..])?);
                 }
-                EventArg::Tuple(args) => self.decode_raw_bytes(args, input, output)?,
-                EventArg::Primitive(name) => {
-                    let result = match name.as_str() {
-                        "DispatchResult" => DispatchResult::decode(input)?,
-                        "DispatchError" => Err(DispatchError::decode(input)?),
-                        _ => {
-                            if let Some(size) = self.type_sizes.get(name) {
-                                let mut buf = vec![0; *size];
-                                input.read(&mut buf)?;
-                                output.write(&buf);
-                                Ok(())
-                            } else {
-                                return Err(Error::TypeSizeUnavailable(name.to_owned()))
-                            }
-                        }
-                    };
-                    if let Err(error) = result {
-                        return Err(
-                            RuntimeError::from_dispatch(&self.metadata, error)?.into()
-                        )
+                EventArg::Primitive(name) if name == "DispatchResult" => {
+                    if let Err(error) = DispatchResult::decode(&mut &bytes[..])? {
+                        dispatch_error = Some(error);
                     }
                 }
+                _ => {}
             }
+            output.extend_from_slice(bytes);
+        }
+        Ok(dispatch_error)
+    }
+
+    /// Decodes the raw data of an event into values, one for each of its arguments.
+    pub fn decode_event_values(&self, event: &RawEvent) -> Result<Vec<Value>, Error> {
+        let metadata = self.metadata();
+        let module = metadata
+            .modules_with_events()
+            .find(|module| module.name() == event.module)
+            .ok_or_else(|| MetadataError::ModuleNotFound(event.module.clone()))?;
+        let event_metadata = module
+            .events()
+            .find(|metadata| metadata.name == event.variant)
+            .ok_or_else(|| {
+                Error::Other(format!(
+                    "Event {}::{} not found",
+                    event.module, event.variant
+                ))
+            })?;
+        let mut input = &event.data[..];
+        let mut values = Vec::new();
+        for arg in event_metadata.arguments() {
+            values.push(self.types.decode(&arg, &mut input)?);
+        }
+        if !input.is_empty() {
+            return Err(Error::Other(format!(
+                "{} bytes left after decoding event {}::{}",
+                input.len(),
+                event.module,
+                event.variant
+            )))
         }
-        Ok(())
+        Ok(values)
     }
 
     /// Decode events.
     pub fn decode_events(&self, input: &mut &[u8]) -> Result<Vec<(Phase, Raw)>, Error> {
+        Ok(self
+            .decode_event_records(input)?
+            .into_iter()
+            .map(|record| (record.phase, record.raw))
+            .collect())
+    }
+
+    /// Decode event records, including the index and topics of every event.
+    pub fn decode_event_records(
+        &self,
+        input: &mut &[u8],
+    ) -> Result<Vec<EventRecord<T>>, Error> {
+        let metadata = self.metadata();
         let compact_len = <Compact<u32>>::decode(input)?;
         let len = compact_len.0 as usize;
 
         let mut r = Vec::new();
-        for _ in 0..len {
+        for index in 0..len {
             // decode EventRecord
             let phase = Phase::decode(input)?;
             let module_variant = input.read_byte()?;
 
-            let module = self.metadata.module_with_events(module_variant)?;
+            let module = metadata.module_with_events(module_variant)?;
             let event_variant = input.read_byte()?;
             let event_metadata = module.event(event_variant)?;
 
@@ -203,36 +314,38 @@ impl<T: System> EventsDecoder<T> {
             );
 
             let mut event_data = Vec::<u8>::new();
-            let result = self.decode_raw_bytes(
-                &event_metadata.arguments(),
-                input,
-                &mut event_data,
-            );
-            let raw = match result {
-                Ok(()) => {
-                    log::debug!("raw bytes: {}", hex::encode(&event_data),);
-
-                    let event = RawEvent {
-                        module: module.name().to_string(),
-                        variant: event_metadata.name.clone(),
-                        data: event_data,
-                    };
-
-                    // topics come after the event data in EventRecord
-                    let _topics = Vec::<T::Hash>::decode(input)?;
-                    Raw::Event(event)
-                }
-                Err(Error::Runtime(err)) => Raw::Error(err),
-                Err(err) => return Err(err),
+            let dispatch_error =
+                self.decode_args(&event_metadata.arguments(), input, &mut event_data)?;
+            log::debug!("raw bytes: {}", hex::encode(&event_data),);
+
+            // topics come after the event data in EventRecord
+            let topics = Vec::<T::Hash>::decode(input)?;
+            let raw = if let Some(error) = dispatch_error {
+                Raw::Error(RuntimeError::from_dispatch(&metadata, error)?)
+            } else {
+                Raw::Event(RawEvent {
+                    module: module.name().to_string(),
+                    variant: event_metadata.name.clone(),
+                    data: event_data,
+                })
             };
 
-            r.push((phase, raw));
+            r.push(EventRecord {
+                phase,
+                index: index as u32,
+                raw,
+                topics,
+            });
         }
         Ok(r)
     }
 }
 
+/// Raw event or error event
+#[derive(Debug)]
 pub enum Raw {
+    /// Event
     Event(RawEvent),
+    /// Error
     Error(RuntimeError),
 }
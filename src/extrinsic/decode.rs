@@ -0,0 +1,88 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of substrate-subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with substrate-subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+use codec::Decode;
+
+use super::UncheckedExtrinsic;
+use crate::{
+    frame::DecodeCall,
+    metadata::Metadata,
+    runtimes::Runtime,
+    Error,
+};
+
+/// Raw bytes of a call, resolved via the metadata.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawCall {
+    /// The name of the module of the call.
+    pub module: String,
+    /// The name of the call.
+    pub function: String,
+    /// The encoded arguments of the call.
+    pub data: Vec<u8>,
+}
+
+/// Extrinsic decoded from a block.
+pub struct DecodedExtrinsic<T: Runtime> {
+    /// The extrinsic, including its signer, signature and extra if it is signed.
+    pub extrinsic: UncheckedExtrinsic<T>,
+    /// The call of the extrinsic.
+    pub call: RawCall,
+}
+
+impl<T: Runtime> DecodedExtrinsic<T> {
+    /// Decodes an encoded extrinsic, resolving its call via the metadata.
+    pub fn decode(metadata: &Metadata, bytes: &[u8]) -> Result<Self, Error> {
+        let extrinsic = UncheckedExtrinsic::<T>::decode(&mut &bytes[..])?;
+        let (module_index, call_index, data) = match &extrinsic.function.0[..] {
+            [module_index, call_index, data @ ..] => (*module_index, *call_index, data),
+            _ => return Err("Extrinsic call is missing its indices".into()),
+        };
+        let (module, call) = metadata.call_by_index(module_index, call_index)?;
+        let call = RawCall {
+            module: module.name().to_string(),
+            function: call.name().to_string(),
+            data: data.to_vec(),
+        };
+        Ok(Self { extrinsic, call })
+    }
+
+    /// Decodes the arguments of the call into the type `C`.
+    /// Returns `None` if the extrinsic calls another function.
+    pub fn as_call<C: DecodeCall<T>>(&self) -> Result<Option<C>, Error> {
+        if self.call.module == C::MODULE && self.call.function == C::FUNCTION {
+            Ok(Some(decode_call_args::<T, C>(&self.call.data)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Decodes the arguments of a call, all of which must be consumed.
+pub(crate) fn decode_call_args<T, C: DecodeCall<T>>(data: &[u8]) -> Result<C, Error> {
+    let input = &mut &data[..];
+    let call = C::decode(input)?;
+    if !input.is_empty() {
+        return Err(format!(
+            "{} bytes left after decoding the arguments of {}::{}",
+            input.len(),
+            C::MODULE,
+            C::FUNCTION
+        )
+        .into())
+    }
+    Ok(call)
+}
@@ -39,6 +39,9 @@ use crate::{
 /// Extra type.
 pub type Extra<T> = <<T as Runtime>::Extra as SignedExtra<T>>::Extra;
 
+/// Tip type.
+pub type Tip<T> = <<T as Runtime>::Extra as SignedExtra<T>>::Tip;
+
 /// SignedExtra checks copied from substrate, in order to remove requirement to implement
 /// substrate's `frame_system::Trait`
 
@@ -138,14 +141,14 @@ where
 ///
 /// # Note
 ///
-/// This is modified from the substrate version to allow passing in of the genesis hash, which is
-/// returned via `additional_signed()`. It assumes therefore `Era::Immortal` (The transaction is
-/// valid forever)
+/// This is modified from the substrate version to allow passing in of the hash of the block the
+/// era starts at, which is returned via `additional_signed()`. For `Era::Immortal` this is the
+/// genesis hash.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
 pub struct CheckEra<T: System>(
     /// The default structure for the Extra encoding
     pub (Era, PhantomData<T>),
-    /// Local genesis hash to be used for `AdditionalSigned`
+    /// Local era checkpoint hash to be used for `AdditionalSigned`
     #[codec(skip)]
     pub T::Hash,
 );
@@ -231,13 +234,21 @@ where
 pub trait SignedExtra<T: System>: SignedExtension {
     /// The type the extras.
     type Extra: SignedExtension + Send + Sync;
+    /// The type of the tip paid to the block author.
+    type Tip: Default + Send + Sync;
 
     /// Creates a new `SignedExtra`.
+    ///
+    /// `era_hash` is the hash of the block the `era` starts at, or the genesis hash for an
+    /// immortal era. `tip` is paid to the block author in addition to the transaction fee.
     fn new(
         spec_version: u32,
         tx_version: u32,
         nonce: T::Index,
         genesis_hash: T::Hash,
+        era: Era,
+        era_hash: T::Hash,
+        tip: Self::Tip,
     ) -> Self;
 
     /// Returns the transaction extra.
@@ -246,11 +257,14 @@ pub trait SignedExtra<T: System>: SignedExtension {
 
 /// Default `SignedExtra` for substrate runtimes.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
-pub struct DefaultExtra<T: System> {
+pub struct DefaultExtra<T: System + Balances> {
     spec_version: u32,
     tx_version: u32,
     nonce: T::Index,
     genesis_hash: T::Hash,
+    era: Era,
+    era_hash: T::Hash,
+    tip: T::Balance,
 }
 
 impl<T: System + Balances + Clone + Debug + Eq + Send + Sync> SignedExtra<T>
@@ -265,18 +279,25 @@ impl<T: System + Balances + Clone + Debug + Eq + Send + Sync> SignedExtra<T>
         CheckWeight<T>,
         ChargeTransactionPayment<T>,
     );
+    type Tip = T::Balance;
 
     fn new(
         spec_version: u32,
         tx_version: u32,
         nonce: T::Index,
         genesis_hash: T::Hash,
+        era: Era,
+        era_hash: T::Hash,
+        tip: T::Balance,
     ) -> Self {
         DefaultExtra {
             spec_version,
             tx_version,
             nonce,
             genesis_hash,
+            era,
+            era_hash,
+            tip,
         }
     }
 
@@ -285,10 +306,10 @@ impl<T: System + Balances + Clone + Debug + Eq + Send + Sync> SignedExtra<T>
             CheckSpecVersion(PhantomData, self.spec_version),
             CheckTxVersion(PhantomData, self.tx_version),
             CheckGenesis(PhantomData, self.genesis_hash),
-            CheckEra((Era::Immortal, PhantomData), self.genesis_hash),
+            CheckEra((self.era, PhantomData), self.era_hash),
             CheckNonce(self.nonce),
             CheckWeight(PhantomData),
-            ChargeTransactionPayment(<T as Balances>::Balance::default()),
+            ChargeTransactionPayment(self.tip),
         )
     }
 }
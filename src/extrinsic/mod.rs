@@ -16,14 +16,20 @@
 
 //! Create signed or unsigned extrinsics.
 
+mod decode;
 mod extra;
 mod signer;
 
 pub use self::{
+    decode::{
+        DecodedExtrinsic,
+        RawCall,
+    },
     extra::{
         DefaultExtra,
         Extra,
         SignedExtra,
+        Tip,
     },
     signer::{
         PairSigner,
@@ -31,7 +37,12 @@ pub use self::{
     },
 };
 
-use sp_runtime::traits::SignedExtension;
+pub(crate) use self::decode::decode_call_args;
+
+use sp_runtime::{
+    generic::Era,
+    traits::SignedExtension,
+};
 use sp_version::RuntimeVersion;
 
 use crate::{
@@ -53,10 +64,16 @@ pub type UncheckedExtrinsic<T> = sp_runtime::generic::UncheckedExtrinsic<
 pub type SignedPayload<T> = sp_runtime::generic::SignedPayload<Encoded, Extra<T>>;
 
 /// Creates a signed extrinsic
+///
+/// `era_hash` is the hash of the block the `era` starts at, or the genesis hash for an immortal
+/// era.
 pub async fn create_signed<T>(
     runtime_version: &RuntimeVersion,
     genesis_hash: T::Hash,
     nonce: T::Index,
+    era: Era,
+    era_hash: T::Hash,
+    tip: Tip<T>,
     call: Encoded,
     signer: &(dyn Signer<T> + Send + Sync),
 ) -> Result<UncheckedExtrinsic<T>, Error>
@@ -67,7 +84,15 @@ where
 {
     let spec_version = runtime_version.spec_version;
     let tx_version = runtime_version.transaction_version;
-    let extra: T::Extra = T::Extra::new(spec_version, tx_version, nonce, genesis_hash);
+    let extra: T::Extra = T::Extra::new(
+        spec_version,
+        tx_version,
+        nonce,
+        genesis_hash,
+        era,
+        era_hash,
+        tip,
+    );
     let payload = SignedPayload::<T>::new(call, extra.extra())?;
     let signed = signer.sign(payload).await?;
     Ok(signed)
@@ -28,7 +28,7 @@ use core::marker::PhantomData;
 use frame_support::Parameter;
 use sp_runtime::traits::{
     AtLeast32Bit,
-    MaybeSerialize,
+    MaybeSerializeDeserialize,
     Member,
 };
 use std::fmt::Debug;
@@ -43,7 +43,7 @@ pub trait Balances: System {
         + codec::Codec
         + Default
         + Copy
-        + MaybeSerialize
+        + MaybeSerializeDeserialize
         + Debug
         + From<<Self as System>::BlockNumber>;
 }
@@ -80,6 +80,14 @@ pub struct TotalIssuanceStore<T: Balances> {
     pub _runtime: PhantomData<T>,
 }
 
+/// The minimum balance required to keep an account alive.
+#[derive(Clone, Debug, Eq, PartialEq, Constant)]
+pub struct ExistentialDepositConstant<T: Balances> {
+    #[constant(value = T::Balance)]
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+}
+
 /// Transfer some liquid free balance to another account.
 ///
 /// `transfer` will set the `FreeBalance` of the sender and receiver.
@@ -95,6 +103,16 @@ pub struct TransferCall<'a, T: Balances> {
     pub amount: T::Balance,
 }
 
+/// Owned counterpart of `TransferCall`, which an encoded transfer decodes into.
+#[derive(Clone, Debug, PartialEq, Decode, DecodeCall)]
+pub struct OwnedTransferCall<T: Balances> {
+    /// Destination of the transfer.
+    pub to: <T as System>::Address,
+    /// Amount to transfer.
+    #[codec(compact)]
+    pub amount: T::Balance,
+}
+
 /// Transfer event.
 #[derive(Clone, Debug, Eq, PartialEq, Event, Decode)]
 pub struct TransferEvent<T: Balances> {
@@ -172,6 +190,13 @@ mod tests {
         assert_ne!(total_issuance, 0);
     }
 
+    #[async_std::test]
+    async fn test_existential_deposit() {
+        env_logger::try_init().ok();
+        let (client, _) = test_client().await;
+        assert_eq!(client.existential_deposit().unwrap(), 500);
+    }
+
     #[async_std::test]
     async fn test_state_read_free_balance() {
         env_logger::try_init().ok();
@@ -205,6 +230,84 @@ mod tests {
         }
     }
 
+    #[async_std::test]
+    async fn test_transfer_and_finalize() {
+        env_logger::try_init().ok();
+        let alice = PairSigner::new(AccountKeyring::Alice.pair());
+        let bob = AccountKeyring::Bob.to_account_id();
+        let (client, _) = test_client().await;
+        let result = client
+            .transfer_and_finalize(&alice, &bob, 10_000)
+            .await
+            .unwrap();
+        let event = result
+            .find_event::<TransferEvent<TestRuntime>>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.to, bob);
+        assert_eq!(event.amount, 10_000);
+        let finalized = client.finalized_head().await.unwrap();
+        let finalized_number = client
+            .header(Some(finalized))
+            .await
+            .unwrap()
+            .unwrap()
+            .number;
+        let block_number = client
+            .header(Some(result.block))
+            .await
+            .unwrap()
+            .unwrap()
+            .number;
+        assert!(block_number <= finalized_number);
+    }
+
+    #[async_std::test]
+    async fn test_dry_run() {
+        env_logger::try_init().ok();
+        let alice = PairSigner::new(AccountKeyring::Alice.pair());
+        let hans = PairSigner::new(Pair::generate().0);
+        let (client, _) = test_client().await;
+        client
+            .transfer_and_watch(&alice, hans.account_id(), 100_000_000_000)
+            .await
+            .unwrap();
+
+        let alice_account = alice.account_id().clone().into();
+        let xt = client
+            .create_signed(
+                TransferCall {
+                    to: &alice_account,
+                    amount: 1,
+                },
+                &hans,
+            )
+            .await
+            .unwrap();
+        client.dry_run(xt, None).await.unwrap();
+
+        let xt = client
+            .create_signed(
+                TransferCall {
+                    to: &alice_account,
+                    amount: 100_000_000_000,
+                },
+                &hans,
+            )
+            .await
+            .unwrap();
+        let res = client.dry_run(xt, None).await;
+        if let Err(Error::Runtime(RuntimeError::Module(error))) = res {
+            let error2 = ModuleError {
+                module: "Balances".into(),
+                error: "InsufficientBalance".into(),
+            };
+            assert_eq!(error, error2);
+        } else {
+            panic!("expected an error");
+        }
+    }
+
     #[async_std::test]
     async fn test_transfer_subscription() {
         env_logger::try_init().ok();
@@ -212,7 +315,7 @@ mod tests {
         let bob = AccountKeyring::Bob.to_account_id();
         let (client, _) = test_client().await;
         let sub = client.subscribe_events().await.unwrap();
-        let mut decoder = EventsDecoder::<TestRuntime>::new(client.metadata().clone());
+        let mut decoder = EventsDecoder::<TestRuntime>::new((*client.metadata()).clone());
         decoder.with_balances();
         let mut sub = EventSubscription::<TestRuntime>::new(sub, decoder);
         sub.filter_event::<TransferEvent<_>>();
@@ -33,6 +33,7 @@ pub mod balances;
 pub mod contracts;
 pub mod sudo;
 pub mod system;
+pub mod transaction_payment;
 
 /// Store trait.
 pub trait Store<T>: Encode {
@@ -55,6 +56,23 @@ pub trait Store<T>: Encode {
     }
 }
 
+/// Constant trait.
+pub trait Constant<T> {
+    /// Module name.
+    const MODULE: &'static str;
+    /// Constant name.
+    const CONSTANT: &'static str;
+    /// Value type.
+    type Value: Decode;
+    /// Returns the value of the constant.
+    fn value(metadata: &Metadata) -> Result<Self::Value, MetadataError> {
+        metadata
+            .module(Self::MODULE)?
+            .constant(Self::CONSTANT)?
+            .value()
+    }
+}
+
 /// Call trait.
 pub trait Call<T>: Encode {
     /// Module name.
@@ -65,6 +83,15 @@ pub trait Call<T>: Encode {
     fn events_decoder(_decoder: &mut EventsDecoder<T>) {}
 }
 
+/// Call which can be decoded, usually the owned counterpart of a `Call` borrowing its
+/// arguments.
+pub trait DecodeCall<T>: Decode {
+    /// Module name.
+    const MODULE: &'static str;
+    /// Function name.
+    const FUNCTION: &'static str;
+}
+
 /// Event trait.
 pub trait Event<T>: Decode {
     /// Module name.
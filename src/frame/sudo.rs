@@ -23,7 +23,10 @@ use crate::{
     },
     Encoded,
 };
-use codec::Encode;
+use codec::{
+    Decode,
+    Encode,
+};
 use core::marker::PhantomData;
 
 /// The subset of the `frame_sudo::Trait` that a client must implement.
@@ -39,6 +42,27 @@ pub struct SudoCall<'a, T: Sudo> {
     pub call: &'a Encoded,
 }
 
+/// Owned counterpart of `SudoCall`, which an encoded sudo call decodes into.
+#[derive(Clone, Debug, Eq, PartialEq, Decode, DecodeCall)]
+pub struct OwnedSudoCall<T: Sudo> {
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+    /// Encoded transaction.
+    pub call: Encoded,
+}
+
+/// Execute a transaction with sudo permissions, declaring its weight instead of
+/// the weight of the transaction, like for a runtime upgrade.
+#[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
+pub struct SudoUncheckedWeightCall<'a, T: Sudo> {
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+    /// Encoded transaction.
+    pub call: &'a Encoded,
+    /// Declared weight of the transaction.
+    pub weight: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,7 +54,7 @@ pub trait System {
     /// transactions associated with a sender account.
     type Index: Parameter
         + Member
-        + MaybeSerialize
+        + MaybeSerializeDeserialize
         + Debug
         + Default
         + MaybeDisplay
@@ -147,6 +147,14 @@ pub struct AccountStore<'a, T: System> {
     pub account_id: &'a T::AccountId,
 }
 
+/// Maximum number of block number to block hash mappings to keep.
+#[derive(Clone, Debug, Eq, PartialEq, Constant)]
+pub struct BlockHashCountConstant<T: System> {
+    #[constant(value = T::BlockNumber)]
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+}
+
 /// Arguments for updating the runtime code
 #[derive(Clone, Debug, Eq, PartialEq, Call, Encode)]
 pub struct SetCodeCall<'a, T: System> {
@@ -163,6 +171,8 @@ pub enum Phase {
     ApplyExtrinsic(u32),
     /// The end.
     Finalization,
+    /// Initializing the block.
+    Initialization,
 }
 
 /// An extrinsic completed successfully.
@@ -205,3 +215,22 @@ pub struct KilledAccountEvent<T: System> {
     /// Killed account id.
     pub account: T::AccountId,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_phase() {
+        let decode = |bytes: &[u8]| Phase::decode(&mut &bytes[..]).unwrap();
+        assert_eq!(decode(&[0, 1, 0, 0, 0]), Phase::ApplyExtrinsic(1));
+        assert_eq!(decode(&[1]), Phase::Finalization);
+        assert_eq!(decode(&[2]), Phase::Initialization);
+    }
+
+    #[async_std::test]
+    async fn test_block_hash_count() {
+        let (client, _) = crate::tests::test_client().await;
+        assert_eq!(client.block_hash_count().unwrap(), 2400);
+    }
+}
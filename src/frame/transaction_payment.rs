@@ -0,0 +1,47 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of substrate-subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with substrate-subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Implements support for the pallet_transaction_payment module.
+
+use crate::frame::balances::{
+    Balances,
+    BalancesEventsDecoder,
+};
+use core::marker::PhantomData;
+
+/// The subset of the `pallet_transaction_payment::Trait` that a client must implement.
+#[module]
+pub trait TransactionPayment: Balances {}
+
+/// The fee to be paid for making a transaction, per byte.
+#[derive(Clone, Debug, Eq, PartialEq, Constant)]
+pub struct TransactionByteFeeConstant<T: TransactionPayment> {
+    #[constant(value = T::Balance)]
+    /// Runtime marker.
+    pub _runtime: PhantomData<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::test_client;
+
+    #[async_std::test]
+    async fn test_transaction_byte_fee() {
+        let (client, _) = test_client().await;
+        assert_eq!(client.transaction_byte_fee().unwrap(), 1);
+    }
+}
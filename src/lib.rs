@@ -48,8 +48,14 @@ pub use substrate_subxt_client as client;
 pub use sp_core;
 pub use sp_runtime;
 
-use codec::Decode;
-use futures::future;
+use codec::{
+    Decode,
+    Encode,
+};
+use futures::{
+    channel::mpsc,
+    future,
+};
 use jsonrpsee::client::Subscription;
 use sc_rpc_api::state::ReadProof;
 use sp_core::{
@@ -61,49 +67,100 @@ use sp_core::{
     Bytes,
 };
 pub use sp_runtime::traits::SignedExtension;
+use sp_runtime::{
+    generic::Era,
+    traits::{
+        Hash,
+        Header,
+        SaturatedConversion,
+    },
+};
 pub use sp_version::RuntimeVersion;
-use std::marker::PhantomData;
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{
+        Arc,
+        Mutex,
+        RwLock,
+    },
+    time::Duration,
+};
 
 mod error;
 mod events;
 pub mod extrinsic;
 mod frame;
 mod metadata;
+mod nonce;
 mod rpc;
 mod runtimes;
 mod subscription;
+mod types;
 
 pub use crate::{
-    error::Error,
+    error::{
+        Error,
+        ModuleError,
+        RuntimeError,
+    },
     events::{
+        EventRecord,
         EventsDecoder,
+        Raw,
         RawEvent,
     },
     extrinsic::{
+        DecodedExtrinsic,
         PairSigner,
+        RawCall,
         SignedExtra,
         Signer,
         UncheckedExtrinsic,
     },
     frame::*,
     metadata::{
+        EventArg,
         Metadata,
         MetadataError,
+        ModuleCallMetadata,
+        ModuleConstantMetadata,
+        ModuleErrorMetadata,
+        ModuleEventMetadata,
+        ModuleMetadata,
+        ModuleWithCalls,
+        ModuleWithErrors,
+        ModuleWithEvents,
+        StorageMetadata,
     },
     rpc::{
         BlockNumber,
         ExtrinsicSuccess,
+        Health,
+        PeerInfo,
+        RuntimeDispatchInfo,
+        SyncState,
+        SystemProperties,
     },
     runtimes::*,
     subscription::*,
     substrate_subxt_proc_macro::*,
+    types::{
+        TypeDef,
+        TypeRegistry,
+        Value,
+    },
 };
 use crate::{
-    frame::system::{
-        AccountStoreExt,
-        Phase,
-        System,
+    extrinsic::Tip,
+    frame::{
+        balances::Balances,
+        system::{
+            Phase,
+            System,
+        },
     },
+    nonce::NonceManager,
     rpc::{
         ChainBlock,
         Rpc,
@@ -117,6 +174,13 @@ pub struct ClientBuilder<T: Runtime> {
     url: Option<String>,
     client: Option<jsonrpsee::Client>,
     page_size: Option<u32>,
+    era_period: Option<u64>,
+    watch_timeout: Option<Duration>,
+    types: Vec<(String, TypeDef)>,
+    metadata: Option<Metadata>,
+    genesis_hash: Option<T::Hash>,
+    runtime_version: Option<RuntimeVersion>,
+    offline: bool,
 }
 
 impl<T: Runtime> ClientBuilder<T> {
@@ -127,6 +191,13 @@ impl<T: Runtime> ClientBuilder<T> {
             url: None,
             client: None,
             page_size: None,
+            era_period: None,
+            watch_timeout: None,
+            types: Vec::new(),
+            metadata: None,
+            genesis_hash: None,
+            runtime_version: None,
+            offline: false,
         }
     }
 
@@ -148,44 +219,247 @@ impl<T: Runtime> ClientBuilder<T> {
         self
     }
 
+    /// Make signed extrinsics mortal, valid for `period` blocks from the current block.
+    ///
+    /// The period is rounded up to a power of two between 4 and 65536. By default extrinsics are
+    /// immortal.
+    pub fn set_era_period(mut self, period: u64) -> Self {
+        self.era_period = Some(period);
+        self
+    }
+
+    /// Set the maximum time to wait for a watched extrinsic to be included in a block,
+    /// or for that block to be finalized when waiting for finality.
+    ///
+    /// By default there is no timeout.
+    pub fn set_watch_timeout(mut self, timeout: Duration) -> Self {
+        self.watch_timeout = Some(timeout);
+        self
+    }
+
+    /// Register the definition of a type used by dynamic values.
+    pub fn register_type(mut self, name: &str, def: TypeDef) -> Self {
+        self.types.push((name.to_string(), def));
+        self
+    }
+
+    /// Use the given metadata instead of fetching it from the node.
+    pub fn set_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Use the given genesis hash instead of fetching it from the node.
+    pub fn set_genesis_hash(mut self, genesis_hash: T::Hash) -> Self {
+        self.genesis_hash = Some(genesis_hash);
+        self
+    }
+
+    /// Use the given runtime version instead of fetching it from the node.
+    pub fn set_runtime_version(mut self, runtime_version: RuntimeVersion) -> Self {
+        self.runtime_version = Some(runtime_version);
+        self
+    }
+
+    /// Build a client that never connects to a node.
+    ///
+    /// The metadata, genesis hash and runtime version must be set. Extrinsics can then
+    /// be created offline with immortal eras and signers with a nonce, while rpc
+    /// requests fail with `Error::Offline`.
+    pub fn set_offline(mut self) -> Self {
+        self.offline = true;
+        self
+    }
+
     /// Creates a new Client.
     pub async fn build(self) -> Result<Client<T>, Error> {
-        let client = if let Some(client) = self.client {
-            client
+        let rpc = if self.offline {
+            Rpc::offline()
         } else {
-            let url = self.url.as_deref().unwrap_or("ws://127.0.0.1:9944");
-            if url.starts_with("ws://") || url.starts_with("wss://") {
-                jsonrpsee::ws_client(url).await?
+            let client = if let Some(client) = self.client {
+                client
             } else {
-                jsonrpsee::http_client(url)
-            }
+                let url = self.url.as_deref().unwrap_or("ws://127.0.0.1:9944");
+                if url.starts_with("ws://") || url.starts_with("wss://") {
+                    jsonrpsee::ws_client(url).await?
+                } else {
+                    jsonrpsee::http_client(url)
+                }
+            };
+            Rpc::new(client)
         };
-        let rpc = Rpc::new(client);
         let (metadata, genesis_hash, runtime_version) = future::join3(
-            rpc.metadata(),
-            rpc.genesis_hash(),
-            rpc.runtime_version(None),
+            async {
+                match self.metadata {
+                    Some(metadata) => Ok(metadata),
+                    None => rpc.metadata().await,
+                }
+            },
+            async {
+                match self.genesis_hash {
+                    Some(genesis_hash) => Ok(genesis_hash),
+                    None => rpc.genesis_hash().await,
+                }
+            },
+            async {
+                match self.runtime_version {
+                    Some(runtime_version) => Ok(runtime_version),
+                    None => rpc.runtime_version(None).await,
+                }
+            },
         )
         .await;
+        let state = Arc::new(RwLock::new(RuntimeState {
+            metadata: Arc::new(metadata?),
+            runtime_version: runtime_version?,
+        }));
+        let metadata_cache = Arc::<RwLock<HashMap<_, _>>>::default();
+        let upgrades = Arc::<Mutex<Vec<_>>>::default();
+        let tracker = if self.offline {
+            None
+        } else {
+            let versions = rpc.subscribe_runtime_version().await?;
+            let runtime_version = state
+                .read()
+                .expect("runtime state lock poisoned")
+                .runtime_version
+                .clone();
+            let (task, handle) = future::abortable(follow_runtime(
+                rpc.clone(),
+                versions,
+                runtime_version,
+                state.clone(),
+                metadata_cache.clone(),
+                upgrades.clone(),
+            ));
+            async_std::task::spawn(task);
+            Some(Arc::new(RuntimeTracker(handle)))
+        };
         Ok(Client {
             rpc,
             genesis_hash: genesis_hash?,
-            metadata: metadata?,
-            runtime_version: runtime_version?,
+            state,
+            metadata_cache,
+            upgrades,
+            _tracker: tracker,
             _marker: PhantomData,
             page_size: self.page_size.unwrap_or(10),
+            era_period: self.era_period,
+            watch_timeout: self.watch_timeout,
+            types: types::runtime_registry::<T>(self.types),
+            nonces: Default::default(),
         })
     }
 }
 
+/// Metadata and version of the runtime, which are replaced together on runtime upgrades.
+#[derive(Clone, Debug)]
+pub(crate) struct RuntimeState {
+    pub(crate) metadata: Arc<Metadata>,
+    pub(crate) runtime_version: RuntimeVersion,
+}
+
+/// Handle of the task following the runtime of the node, which stops it once the
+/// last clone of the client is dropped.
+struct RuntimeTracker(future::AbortHandle);
+
+impl Drop for RuntimeTracker {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Follows the runtime version of the node, switching the client to the metadata and
+/// version of every new runtime before notifying the subscribers of
+/// `Client::on_runtime_upgrade`.
+async fn follow_runtime<T: Runtime>(
+    rpc: Rpc<T>,
+    mut versions: Subscription<RuntimeVersion>,
+    mut last: RuntimeVersion,
+    state: Arc<RwLock<RuntimeState>>,
+    metadata_cache: Arc<RwLock<HashMap<u32, Arc<Metadata>>>>,
+    upgrades: Arc<Mutex<Vec<mpsc::UnboundedSender<RuntimeVersion>>>>,
+) {
+    loop {
+        let version = versions.next().await;
+        if version == last {
+            continue
+        }
+        last = version;
+        let runtime = loop {
+            match fetch_runtime(&rpc, &metadata_cache).await {
+                Ok(runtime) => break runtime,
+                Err(error) => {
+                    log::warn!("Failed to fetch the upgraded runtime: {}", error);
+                    futures_timer::Delay::new(Duration::from_secs(1)).await;
+                }
+            }
+        };
+        let runtime_version = runtime.runtime_version.clone();
+        let previous = {
+            let mut state = state.write().expect("runtime state lock poisoned");
+            if state.runtime_version == runtime_version {
+                continue
+            }
+            std::mem::replace(&mut *state, runtime)
+        };
+        log::info!(
+            "Runtime upgraded from {} to {}",
+            previous.runtime_version.spec_version,
+            runtime_version.spec_version
+        );
+        metadata_cache
+            .write()
+            .expect("metadata cache lock poisoned")
+            .insert(previous.runtime_version.spec_version, previous.metadata);
+        upgrades
+            .lock()
+            .expect("upgrade subscribers lock poisoned")
+            .retain(|sender| sender.unbounded_send(runtime_version.clone()).is_ok());
+    }
+}
+
+/// Fetches the metadata and version of the runtime of the best block.
+async fn fetch_runtime<T: Runtime>(
+    rpc: &Rpc<T>,
+    metadata_cache: &RwLock<HashMap<u32, Arc<Metadata>>>,
+) -> Result<RuntimeState, Error> {
+    // fetch the version and metadata at the same block, which the best block
+    // may have moved past in the meantime
+    let hash = rpc
+        .block_hash(None)
+        .await?
+        .ok_or_else(|| Error::Other("Best block hash not found".into()))?;
+    let runtime_version = rpc.runtime_version(Some(hash)).await?;
+    let cached = metadata_cache
+        .read()
+        .expect("metadata cache lock poisoned")
+        .get(&runtime_version.spec_version)
+        .cloned();
+    let metadata = match cached {
+        Some(metadata) => metadata,
+        None => Arc::new(rpc.metadata_at(hash).await?),
+    };
+    Ok(RuntimeState {
+        metadata,
+        runtime_version,
+    })
+}
+
 /// Client to interface with a substrate node.
 pub struct Client<T: Runtime> {
     rpc: Rpc<T>,
     genesis_hash: T::Hash,
-    metadata: Metadata,
-    runtime_version: RuntimeVersion,
+    state: Arc<RwLock<RuntimeState>>,
+    metadata_cache: Arc<RwLock<HashMap<u32, Arc<Metadata>>>>,
+    upgrades: Arc<Mutex<Vec<mpsc::UnboundedSender<RuntimeVersion>>>>,
+    _tracker: Option<Arc<RuntimeTracker>>,
     _marker: PhantomData<(fn() -> T::Signature, T::Extra)>,
     page_size: u32,
+    era_period: Option<u64>,
+    watch_timeout: Option<Duration>,
+    types: TypeRegistry,
+    nonces: NonceManager<T>,
 }
 
 impl<T: Runtime> Clone for Client<T> {
@@ -193,10 +467,16 @@ impl<T: Runtime> Clone for Client<T> {
         Self {
             rpc: self.rpc.clone(),
             genesis_hash: self.genesis_hash,
-            metadata: self.metadata.clone(),
-            runtime_version: self.runtime_version.clone(),
+            state: self.state.clone(),
+            metadata_cache: self.metadata_cache.clone(),
+            upgrades: self.upgrades.clone(),
+            _tracker: self._tracker.clone(),
             _marker: PhantomData,
             page_size: self.page_size,
+            era_period: self.era_period,
+            watch_timeout: self.watch_timeout,
+            types: self.types.clone(),
+            nonces: self.nonces.clone(),
         }
     }
 }
@@ -254,8 +534,77 @@ impl<T: Runtime> Client<T> {
     }
 
     /// Returns the chain metadata.
-    pub fn metadata(&self) -> &Metadata {
-        &self.metadata
+    ///
+    /// The metadata is replaced when the runtime of the node is upgraded.
+    pub fn metadata(&self) -> Arc<Metadata> {
+        self.state().metadata
+    }
+
+    /// Returns the runtime version.
+    pub fn runtime_version(&self) -> RuntimeVersion {
+        self.state().runtime_version
+    }
+
+    /// Returns the metadata of the runtime at the given block.
+    ///
+    /// The metadata of previous runtimes is cached by spec version, so it is only
+    /// fetched once per runtime.
+    pub async fn metadata_at(&self, hash: T::Hash) -> Result<Arc<Metadata>, Error> {
+        let version = self.rpc.runtime_version(Some(hash)).await?;
+        let state = self.state();
+        if version.spec_version == state.runtime_version.spec_version {
+            return Ok(state.metadata)
+        }
+        let cached = self
+            .metadata_cache
+            .read()
+            .expect("metadata cache lock poisoned")
+            .get(&version.spec_version)
+            .cloned();
+        if let Some(metadata) = cached {
+            return Ok(metadata)
+        }
+        let metadata = Arc::new(self.rpc.metadata_at(hash).await?);
+        self.metadata_cache
+            .write()
+            .expect("metadata cache lock poisoned")
+            .insert(version.spec_version, metadata.clone());
+        Ok(metadata)
+    }
+
+    fn state(&self) -> RuntimeState {
+        self.state
+            .read()
+            .expect("runtime state lock poisoned")
+            .clone()
+    }
+
+    /// Subscribes to runtime upgrades.
+    ///
+    /// The client follows upgrades by itself, and notifies of them once it switched
+    /// to the metadata and runtime version of the new runtime. Offline clients
+    /// don't follow the runtime and return `Error::Offline`.
+    pub fn on_runtime_upgrade(&self) -> Result<RuntimeUpgrades, Error> {
+        if self._tracker.is_none() {
+            return Err(Error::Offline)
+        }
+        let (sender, receiver) = mpsc::unbounded();
+        self.upgrades
+            .lock()
+            .expect("upgrade subscribers lock poisoned")
+            .push(sender);
+        Ok(RuntimeUpgrades::new(receiver))
+    }
+
+    /// Fetch the encoded metadata from the node, to be cached and later loaded with
+    /// `Metadata::from_bytes` or `Metadata::from_file`.
+    pub async fn metadata_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.rpc.metadata_bytes().await
+    }
+
+    /// Returns the type registry used to encode and decode dynamic values.
+    pub fn type_registry(&self) -> &TypeRegistry {
+        &self.types
     }
 
     /// Fetch the value under an unhashed storage key
@@ -277,7 +626,7 @@ impl<T: Runtime> Client<T> {
         store: &F,
         hash: Option<T::Hash>,
     ) -> Result<Option<F::Returns>, Error> {
-        let key = store.key(&self.metadata)?;
+        let key = store.key(&self.metadata())?;
         self.fetch_unhashed::<F::Returns>(key, hash).await
     }
 
@@ -290,8 +639,77 @@ impl<T: Runtime> Client<T> {
         if let Some(data) = self.fetch(store, hash).await? {
             Ok(data)
         } else {
-            Ok(store.default(&self.metadata)?)
+            Ok(store.default(&self.metadata())?)
+        }
+    }
+
+    /// Returns the value of a module constant.
+    pub fn constant<C: Constant<T>>(&self) -> Result<C::Value, Error> {
+        Ok(C::value(&self.metadata())?)
+    }
+
+    /// Fetch a storage entry by the names of its module and entry, with an optional
+    /// block hash.
+    ///
+    /// `keys` holds one key for a map and two for a double map, which are encoded
+    /// and hashed as described by the metadata of the runtime of the block. The value
+    /// is decoded according to its type name, or returned as `Value::Bytes` if its
+    /// type is unknown to the type registry. Entries with a default value return it
+    /// when they are empty.
+    pub async fn fetch_dynamic(
+        &self,
+        module: &str,
+        entry: &str,
+        keys: Vec<Value>,
+        hash: Option<T::Hash>,
+    ) -> Result<Option<Value>, Error> {
+        let metadata = match hash {
+            Some(hash) => self.metadata_at(hash).await?,
+            None => self.metadata(),
+        };
+        let storage = metadata.module(module)?.storage(entry)?;
+        let key_types = storage.key_types()?;
+        if key_types.len() != keys.len() {
+            return Err(format!(
+                "Storage {} expects {} keys, got {}",
+                entry,
+                key_types.len(),
+                keys.len()
+            )
+            .into())
+        }
+        let mut key = storage.prefix().0;
+        for ((hasher, ty), value) in key_types.iter().zip(&keys) {
+            let mut bytes = Vec::new();
+            self.types.encode(ty, value, &mut bytes)?;
+            key.extend(StorageMetadata::hash(hasher, &bytes));
+        }
+        let data = match self.rpc.storage(&StorageKey(key), hash).await? {
+            Some(data) => data.0,
+            None => {
+                match storage.default_bytes() {
+                    Some(default) => default.to_vec(),
+                    None => return Ok(None),
+                }
+            }
+        };
+        let ty = storage.value_type()?;
+        if !self.types.missing(&ty).is_empty() {
+            return Ok(Some(Value::Bytes(data)))
+        }
+        let mut input = &data[..];
+        let value = self.types.decode(&ty, &mut input)?;
+        if !input.is_empty() {
+            return Err(format!(
+                "{} bytes left after decoding storage {}::{} as {:?}",
+                input.len(),
+                module,
+                entry,
+                ty
+            )
+            .into())
         }
+        Ok(Some(value))
     }
 
     /// Returns an iterator of key value pairs.
@@ -325,7 +743,7 @@ impl<T: Runtime> Client<T> {
         start_key: Option<StorageKey>,
         hash: Option<T::Hash>,
     ) -> Result<Vec<StorageKey>, Error> {
-        let prefix = <F as Store<T>>::prefix(&self.metadata)?;
+        let prefix = <F as Store<T>>::prefix(&self.metadata())?;
         let keys = self
             .rpc
             .storage_keys_paged(Some(prefix), count, start_key, hash)
@@ -376,6 +794,33 @@ impl<T: Runtime> Client<T> {
         Ok(block)
     }
 
+    /// Get the extrinsics of a block, decoded with the metadata of its runtime.
+    ///
+    /// Returns `None` if the block is unknown.
+    pub async fn block_extrinsics(
+        &self,
+        hash: T::Hash,
+    ) -> Result<Option<Vec<DecodedExtrinsic<T>>>, Error> {
+        let metadata = self.metadata_at(hash).await?;
+        self.block_extrinsics_with_metadata(hash, &metadata).await
+    }
+
+    pub(crate) async fn block_extrinsics_with_metadata(
+        &self,
+        hash: T::Hash,
+        metadata: &Metadata,
+    ) -> Result<Option<Vec<DecodedExtrinsic<T>>>, Error> {
+        let block = match self.rpc.block(Some(hash)).await? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let mut extrinsics = Vec::new();
+        for extrinsic in &block.block.extrinsics {
+            extrinsics.push(DecodedExtrinsic::decode(&metadata, &extrinsic.encode())?);
+        }
+        Ok(Some(extrinsics))
+    }
+
     /// Get proof of storage entries at a specific block's state.
     pub async fn read_proof<H>(
         &self,
@@ -389,6 +834,35 @@ impl<T: Runtime> Client<T> {
         Ok(proof)
     }
 
+    /// Fetch the events of a block, like the ones of a historical block.
+    ///
+    /// They are decoded with the metadata of the runtime of the block and the types
+    /// registered to the client.
+    pub async fn events_at(&self, hash: T::Hash) -> Result<Vec<EventRecord<T>>, Error> {
+        let metadata = self.metadata_at(hash).await?;
+        self.events_with_metadata(hash, metadata).await
+    }
+
+    pub(crate) async fn events_with_metadata(
+        &self,
+        hash: T::Hash,
+        metadata: Arc<Metadata>,
+    ) -> Result<Vec<EventRecord<T>>, Error> {
+        let decoder = EventsDecoder::with_types(metadata, &self.types);
+        self.rpc.events(Some(hash), &decoder).await
+    }
+
+    /// Fetch the events of a block like `events_at`, using the type sizes known to
+    /// `decoder`.
+    pub async fn events_at_with_decoder(
+        &self,
+        hash: T::Hash,
+        decoder: &EventsDecoder<T>,
+    ) -> Result<Vec<EventRecord<T>>, Error> {
+        let decoder = decoder.with_metadata(self.metadata_at(hash).await?);
+        self.rpc.events(Some(hash), &decoder).await
+    }
+
     /// Subscribe to events.
     pub async fn subscribe_events(
         &self,
@@ -411,14 +885,90 @@ impl<T: Runtime> Client<T> {
         Ok(headers)
     }
 
+    /// Subscribe to finalized blocks, along with their decoded extrinsics and the
+    /// events emitted by each of them.
+    pub async fn subscribe_finalized_blocks_with_events(
+        &self,
+    ) -> Result<FinalizedBlocks<T>, Error> {
+        let headers = self.rpc.subscribe_finalized_blocks().await?;
+        Ok(FinalizedBlocks::new(self.clone(), headers))
+    }
+
     /// Encodes a call.
     pub fn encode<C: Call<T>>(&self, call: C) -> Result<Encoded, Error> {
-        Ok(self
-            .metadata()
+        Self::encode_with(&self.metadata(), call)
+    }
+
+    fn encode_with<C: Call<T>>(metadata: &Metadata, call: C) -> Result<Encoded, Error> {
+        Ok(metadata
             .module_with_calls(C::MODULE)
             .and_then(|module| module.call(C::FUNCTION, call))?)
     }
 
+    /// Decodes an encoded call into `C`, the module and call indices of which must be
+    /// those of `C` in the metadata of the runtime of the given block, or the current
+    /// runtime if `None`.
+    ///
+    /// Returns `None` if the encoded call is another one.
+    pub async fn find_call<C: DecodeCall<T>>(
+        &self,
+        call: &Encoded,
+        hash: Option<T::Hash>,
+    ) -> Result<Option<C>, Error> {
+        let metadata = match hash {
+            Some(hash) => self.metadata_at(hash).await?,
+            None => self.metadata(),
+        };
+        let module = metadata.module_with_calls(C::MODULE)?;
+        let call_index = module.call_metadata(C::FUNCTION)?.index();
+        match &call.0[..] {
+            [module_index, index, data @ ..]
+                if *module_index == module.index() && *index == call_index =>
+            {
+                Ok(Some(extrinsic::decode_call_args::<T, C>(data)?))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Encodes a call of a module, whose arguments are given as dynamic values.
+    ///
+    /// The index and argument types of the call are looked up in the metadata, and the
+    /// arguments encoded with the client's type registry.
+    pub fn dynamic_call(
+        &self,
+        module: &str,
+        function: &str,
+        args: Vec<Value>,
+    ) -> Result<Encoded, Error> {
+        self.dynamic_call_with(&self.metadata(), module, function, args)
+    }
+
+    fn dynamic_call_with(
+        &self,
+        metadata: &Metadata,
+        module: &str,
+        function: &str,
+        args: Vec<Value>,
+    ) -> Result<Encoded, Error> {
+        let module = metadata.module_with_calls(module)?;
+        let call = module.call_metadata(function)?;
+        if call.arguments().len() != args.len() {
+            return Err(format!(
+                "Call {} expects {} arguments, got {}",
+                function,
+                call.arguments().len(),
+                args.len()
+            )
+            .into())
+        }
+        let mut bytes = vec![module.index(), call.index()];
+        for ((_, ty), value) in call.arguments().iter().zip(&args) {
+            self.types.encode(ty, value, &mut bytes)?;
+        }
+        Ok(Encoded(bytes))
+    }
+
     /// Creates an unsigned extrinsic.
     pub fn create_unsigned<C: Call<T> + Send + Sync>(
         &self,
@@ -428,12 +978,93 @@ impl<T: Runtime> Client<T> {
         Ok(extrinsic::create_unsigned::<T>(call))
     }
 
+    /// Returns the era for a new signed extrinsic and the hash of the block it starts at.
+    ///
+    /// Extrinsics are immortal unless an era period was set on the `ClientBuilder`, in which case
+    /// the era starts at the current best block.
+    async fn era(&self) -> Result<(Era, T::Hash), Error> {
+        let period = if let Some(period) = self.era_period {
+            period
+        } else {
+            return Ok((Era::Immortal, self.genesis_hash))
+        };
+        let header = self
+            .rpc
+            .header(None)
+            .await?
+            .ok_or_else(|| Error::Other("Best block header not found".into()))?;
+        let current = (*header.number()).saturated_into::<u64>();
+        let era = Era::mortal(period, current);
+        let birth = era.birth(current);
+        let era_hash = if birth == current {
+            header.hash()
+        } else {
+            self.block_hash(Some(birth.into()))
+                .await?
+                .ok_or_else(|| Error::Other(format!("Block {} not found", birth)))?
+        };
+        Ok((era, era_hash))
+    }
+
     /// Creates a signed extrinsic.
     pub async fn create_signed<C: Call<T> + Send + Sync>(
         &self,
         call: C,
         signer: &(dyn Signer<T> + Send + Sync),
     ) -> Result<UncheckedExtrinsic<T>, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        self.create_signed_with_tip(call, signer, Default::default())
+            .await
+    }
+
+    /// Creates a signed extrinsic which pays `tip` to the block author to gain additional
+    /// priority in the transaction queue.
+    pub async fn create_signed_with_tip<C: Call<T> + Send + Sync>(
+        &self,
+        call: C,
+        signer: &(dyn Signer<T> + Send + Sync),
+        tip: Tip<T>,
+    ) -> Result<UncheckedExtrinsic<T>, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        let runtime = self.state();
+        let call = Self::encode_with(&runtime.metadata, call)?;
+        self.sign_encoded(&runtime, call, signer, tip).await
+    }
+
+    /// Creates a signed extrinsic of a call given by the names of its module and
+    /// function and its arguments as dynamic values, like `dynamic_call` encodes it,
+    /// paying `tip` to the block author.
+    pub async fn create_signed_dynamic(
+        &self,
+        module: &str,
+        function: &str,
+        args: Vec<Value>,
+        signer: &(dyn Signer<T> + Send + Sync),
+        tip: Tip<T>,
+    ) -> Result<UncheckedExtrinsic<T>, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        let runtime = self.state();
+        let call = self.dynamic_call_with(&runtime.metadata, module, function, args)?;
+        self.sign_encoded(&runtime, call, signer, tip).await
+    }
+
+    /// Signs a call encoded with the metadata of `runtime`.
+    async fn sign_encoded(
+        &self,
+        runtime: &RuntimeState,
+        call: Encoded,
+        signer: &(dyn Signer<T> + Send + Sync),
+        tip: Tip<T>,
+    ) -> Result<UncheckedExtrinsic<T>, Error>
     where
         <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
             Send + Sync,
@@ -441,24 +1072,113 @@ impl<T: Runtime> Client<T> {
         let account_nonce = if let Some(nonce) = signer.nonce() {
             nonce
         } else {
-            self.account(signer.account_id(), None).await?.nonce
+            self.nonces.peek(&self.rpc, signer.account_id()).await?
         };
-        let call = self.encode(call)?;
-        let signed = extrinsic::create_signed(
-            &self.runtime_version,
+        self.create_signed_with_nonce(runtime, call, signer, account_nonce, tip)
+            .await
+    }
+
+    /// Creates a signed extrinsic to be submitted, reserving its nonce unless the
+    /// signer has one.
+    async fn sign_for_submission<C: Call<T> + Send + Sync>(
+        &self,
+        call: C,
+        signer: &(dyn Signer<T> + Send + Sync),
+        tip: Tip<T>,
+    ) -> Result<UncheckedExtrinsic<T>, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        let runtime = self.state();
+        let call = Self::encode_with(&runtime.metadata, call)?;
+        if let Some(nonce) = signer.nonce() {
+            return self
+                .create_signed_with_nonce(&runtime, call, signer, nonce, tip)
+                .await
+        }
+        let nonce = self.nonces.next(&self.rpc, signer.account_id()).await?;
+        let result = self
+            .create_signed_with_nonce(&runtime, call, signer, nonce, tip)
+            .await;
+        if result.is_err() {
+            // the extrinsic won't be submitted, so its nonce is free again
+            self.nonces.reset(signer.account_id()).await;
+        }
+        result
+    }
+
+    async fn create_signed_with_nonce(
+        &self,
+        runtime: &RuntimeState,
+        call: Encoded,
+        signer: &(dyn Signer<T> + Send + Sync),
+        nonce: T::Index,
+        tip: Tip<T>,
+    ) -> Result<UncheckedExtrinsic<T>, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        let (era, era_hash) = self.era().await?;
+        extrinsic::create_signed(
+            &runtime.runtime_version,
             self.genesis_hash,
-            account_nonce,
+            nonce,
+            era,
+            era_hash,
+            tip,
             call,
             signer,
         )
-        .await?;
-        Ok(signed)
+        .await
+    }
+
+    /// Estimates the fee of a call by signing it like `create_signed` does and
+    /// querying its dispatch info from the node.
+    ///
+    /// No nonce is reserved for the signer, so the call can be submitted afterwards.
+    pub async fn estimate_fee<C: Call<T> + Send + Sync>(
+        &self,
+        call: C,
+        signer: &(dyn Signer<T> + Send + Sync),
+    ) -> Result<RuntimeDispatchInfo<T::Balance>, Error>
+    where
+        T: Balances,
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        let nonce = if let Some(nonce) = signer.nonce() {
+            nonce
+        } else {
+            self.nonces.peek(&self.rpc, signer.account_id()).await?
+        };
+        let runtime = self.state();
+        let call = Self::encode_with(&runtime.metadata, call)?;
+        let extrinsic = self
+            .create_signed_with_nonce(&runtime, call, signer, nonce, Default::default())
+            .await?;
+        self.rpc.query_info(extrinsic, None).await
+    }
+
+    /// Resyncs the nonce of a signer that relies on the client's nonce management
+    /// after the transaction pool rejected its extrinsic, which leaves the nonce
+    /// reserved for it unused.
+    async fn resync_nonce<R>(
+        &self,
+        signer: &(dyn Signer<T> + Send + Sync),
+        result: &Result<R, Error>,
+    ) {
+        if let Err(error) = result {
+            if error.is_pool_rejection() && signer.nonce().is_none() {
+                self.nonces.reset(signer.account_id()).await;
+            }
+        }
     }
 
     /// Returns an events decoder for a call.
     pub fn events_decoder<C: Call<T>>(&self) -> EventsDecoder<T> {
-        let metadata = self.metadata().clone();
-        let mut decoder = EventsDecoder::new(metadata);
+        let mut decoder = EventsDecoder::for_runtime(self.state.clone());
         C::events_decoder(&mut decoder);
         decoder
     }
@@ -477,53 +1197,233 @@ impl<T: Runtime> Client<T> {
         extrinsic: UncheckedExtrinsic<T>,
         decoder: EventsDecoder<T>,
     ) -> Result<ExtrinsicSuccess<T>, Error> {
-        self.rpc
-            .submit_and_watch_extrinsic(extrinsic, decoder)
+        self.watch_extrinsic(extrinsic, decoder)
+            .await?
+            .wait_for_in_block()
             .await
     }
 
-    /// Submits a transaction to the chain.
-    pub async fn submit<C: Call<T> + Send + Sync>(
+    /// Submits an extrinsic and returns a stream of its progress.
+    pub async fn watch_extrinsic(
+        &self,
+        extrinsic: UncheckedExtrinsic<T>,
+        decoder: EventsDecoder<T>,
+    ) -> Result<TransactionProgress<T>, Error> {
+        let ext_hash = T::Hashing::hash_of(&extrinsic);
+        log::info!("Submitting Extrinsic `{:?}`", ext_hash);
+        let subscription = self.rpc.watch_extrinsic(extrinsic).await?;
+        Ok(TransactionProgress::new(
+            self.rpc.clone(),
+            subscription,
+            decoder,
+            ext_hash,
+            self.watch_timeout,
+        ))
+    }
+
+    /// Submits a transaction to the chain and returns a stream of its progress.
+    pub async fn watch_progress<C: Call<T> + Send + Sync>(
         &self,
         call: C,
         signer: &(dyn Signer<T> + Send + Sync),
-    ) -> Result<T::Hash, Error>
+    ) -> Result<TransactionProgress<T>, Error>
     where
         <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
             Send + Sync,
     {
-        let extrinsic = self.create_signed(call, signer).await?;
-        self.submit_extrinsic(extrinsic).await
+        self.watch_signed(call, signer, Default::default()).await
     }
 
-    /// Submits transaction to the chain and watch for events.
-    pub async fn watch<C: Call<T> + Send + Sync>(
+    async fn watch_signed<C: Call<T> + Send + Sync>(
         &self,
         call: C,
         signer: &(dyn Signer<T> + Send + Sync),
-    ) -> Result<ExtrinsicSuccess<T>, Error>
+        tip: Tip<T>,
+    ) -> Result<TransactionProgress<T>, Error>
     where
         <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
             Send + Sync,
     {
-        let extrinsic = self.create_signed(call, signer).await?;
+        let extrinsic = self.sign_for_submission(call, signer, tip).await?;
         let decoder = self.events_decoder::<C>();
-        self.submit_and_watch_extrinsic(extrinsic, decoder).await
+        let result = self.watch_extrinsic(extrinsic, decoder).await;
+        self.resync_nonce(signer, &result).await;
+        let progress = result?;
+        if signer.nonce().is_some() {
+            return Ok(progress)
+        }
+        Ok(progress.resync_nonce_of(self.nonces.clone(), signer.account_id().clone()))
     }
 
-    /// Insert a key into the keystore.
-    pub async fn insert_key(
+    /// Submits a transaction to the chain.
+    pub async fn submit<C: Call<T> + Send + Sync>(
         &self,
-        key_type: String,
-        suri: String,
-        public: Bytes,
-    ) -> Result<(), Error> {
-        self.rpc.insert_key(key_type, suri, public).await
-    }
-
-    /// Generate new session keys and returns the corresponding public keys.
-    pub async fn rotate_keys(&self) -> Result<Bytes, Error> {
-        self.rpc.rotate_keys().await
+        call: C,
+        signer: &(dyn Signer<T> + Send + Sync),
+    ) -> Result<T::Hash, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        self.submit_with_tip(call, signer, Default::default()).await
+    }
+
+    /// Submits a transaction to the chain, paying `tip` to the block author.
+    pub async fn submit_with_tip<C: Call<T> + Send + Sync>(
+        &self,
+        call: C,
+        signer: &(dyn Signer<T> + Send + Sync),
+        tip: Tip<T>,
+    ) -> Result<T::Hash, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        let extrinsic = self.sign_for_submission(call, signer, tip).await?;
+        let result = self.submit_extrinsic(extrinsic).await;
+        self.resync_nonce(signer, &result).await;
+        result
+    }
+
+    /// Submits transaction to the chain and watch for events.
+    pub async fn watch<C: Call<T> + Send + Sync>(
+        &self,
+        call: C,
+        signer: &(dyn Signer<T> + Send + Sync),
+    ) -> Result<ExtrinsicSuccess<T>, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        self.watch_with_tip(call, signer, Default::default()).await
+    }
+
+    /// Submits transaction to the chain, paying `tip` to the block author, and watch for events.
+    pub async fn watch_with_tip<C: Call<T> + Send + Sync>(
+        &self,
+        call: C,
+        signer: &(dyn Signer<T> + Send + Sync),
+        tip: Tip<T>,
+    ) -> Result<ExtrinsicSuccess<T>, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        self.watch_signed(call, signer, tip)
+            .await?
+            .wait_for_in_block()
+            .await
+    }
+
+    /// Fetch the next index of an account, taking pending transactions in the
+    /// transaction pool into account.
+    pub async fn system_account_next_index(
+        &self,
+        account: &T::AccountId,
+    ) -> Result<T::Index, Error> {
+        self.rpc.system_account_next_index(account).await
+    }
+
+    /// Fetch the health of the node.
+    pub async fn system_health(&self) -> Result<Health, Error> {
+        self.rpc.system_health().await
+    }
+
+    /// Fetch the peers the node is connected to.
+    pub async fn system_peers(
+        &self,
+    ) -> Result<Vec<PeerInfo<T::Hash, T::BlockNumber>>, Error> {
+        self.rpc.system_peers().await
+    }
+
+    /// Fetch the name of the chain.
+    pub async fn system_chain(&self) -> Result<String, Error> {
+        self.rpc.system_chain().await
+    }
+
+    /// Fetch the name of the node implementation.
+    pub async fn system_name(&self) -> Result<String, Error> {
+        self.rpc.system_name().await
+    }
+
+    /// Fetch the version of the node implementation.
+    pub async fn system_version(&self) -> Result<String, Error> {
+        self.rpc.system_version().await
+    }
+
+    /// Fetch the properties of the chain, like the decimals and symbol of its token.
+    pub async fn system_properties(&self) -> Result<SystemProperties, Error> {
+        self.rpc.system_properties().await
+    }
+
+    /// Fetch the block synchronization state of the node.
+    pub async fn system_sync_state(&self) -> Result<SyncState<T::BlockNumber>, Error> {
+        self.rpc.system_sync_state().await
+    }
+
+    /// Fetch the state of the network.
+    ///
+    /// This is an unsafe RPC method.
+    pub async fn system_network_state(&self) -> Result<serde_json::Value, Error> {
+        self.rpc.system_network_state().await
+    }
+
+    /// Dry runs an extrinsic at the given block, or the best block if `None`,
+    /// without submitting it.
+    ///
+    /// Returns `Error::Invalid` if the extrinsic would not be valid, and
+    /// `Error::Runtime` if its dispatch would fail.
+    pub async fn dry_run(
+        &self,
+        extrinsic: UncheckedExtrinsic<T>,
+        at: Option<T::Hash>,
+    ) -> Result<(), Error> {
+        match self.rpc.dry_run(extrinsic, at).await?? {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                let metadata = match at {
+                    Some(hash) => self.metadata_at(hash).await?,
+                    None => self.metadata(),
+                };
+                Err(RuntimeError::from_dispatch(&metadata, error)?.into())
+            }
+        }
+    }
+
+    /// Submits transaction to the chain and waits until the block including it is
+    /// finalized.
+    ///
+    /// The returned events are the ones emitted in the finalized block. If the
+    /// transaction gets retracted, it keeps being tracked until it is included in a
+    /// block that gets finalized.
+    pub async fn finalize<C: Call<T> + Send + Sync>(
+        &self,
+        call: C,
+        signer: &(dyn Signer<T> + Send + Sync),
+    ) -> Result<ExtrinsicSuccess<T>, Error>
+    where
+        <<T::Extra as SignedExtra<T>>::Extra as SignedExtension>::AdditionalSigned:
+            Send + Sync,
+    {
+        self.watch_progress(call, signer)
+            .await?
+            .wait_for_finalized()
+            .await
+    }
+
+    /// Insert a key into the keystore.
+    pub async fn insert_key(
+        &self,
+        key_type: String,
+        suri: String,
+        public: Bytes,
+    ) -> Result<(), Error> {
+        self.rpc.insert_key(key_type, suri, public).await
+    }
+
+    /// Generate new session keys and returns the corresponding public keys.
+    pub async fn rotate_keys(&self) -> Result<Bytes, Error> {
+        self.rpc.rotate_keys().await
     }
 
     /// Checks if the keystore has private keys for the given session public keys.
@@ -558,14 +1458,43 @@ impl codec::Encode for Encoded {
     }
 }
 
+impl codec::Decode for Encoded {
+    fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+        // the length of a call isn't encoded, so it spans the rest of the input
+        let len = input
+            .remaining_len()?
+            .ok_or("Remaining length of the encoded call unknown")?;
+        let mut bytes = vec![0; len];
+        input.read(&mut bytes)?;
+        Ok(Encoded(bytes))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        error::POOL_INVALID_TX,
+        frame::{
+            balances::{
+                TotalIssuanceStoreExt,
+                TransferCallExt,
+            },
+            sudo::SudoUncheckedWeightCallExt,
+            system::AccountStoreExt,
+        },
+    };
+    use frame_support::weights::DispatchClass;
+    use jsonrpsee::{
+        client::RequestError,
+        common::ErrorCode,
+    };
     use sp_core::storage::{
         well_known_keys,
         StorageKey,
     };
     use sp_keyring::AccountKeyring;
+    use sp_transaction_pool::TransactionStatus;
     use substrate_subxt_client::{
         DatabaseConfig,
         KeystoreConfig,
@@ -577,9 +1506,9 @@ mod tests {
 
     pub(crate) type TestRuntime = crate::NodeTemplateRuntime;
 
-    pub(crate) async fn test_client_with(
+    pub(crate) fn test_client_builder(
         key: AccountKeyring,
-    ) -> (Client<TestRuntime>, TempDir) {
+    ) -> (ClientBuilder<TestRuntime>, TempDir) {
         env_logger::try_init().ok();
         let tmp = TempDir::new("subxt-").expect("failed to create tempdir");
         let config = SubxtClientConfig {
@@ -599,15 +1528,20 @@ mod tests {
             role: Role::Authority(key),
             telemetry: None,
         };
-        let client = ClientBuilder::new()
+        let builder = ClientBuilder::new()
             .set_client(
                 SubxtClient::from_config(config, test_node::service::new_full)
                     .expect("Error creating subxt client"),
             )
-            .set_page_size(3)
-            .build()
-            .await
-            .expect("Error creating client");
+            .set_page_size(3);
+        (builder, tmp)
+    }
+
+    pub(crate) async fn test_client_with(
+        key: AccountKeyring,
+    ) -> (Client<TestRuntime>, TempDir) {
+        let (builder, tmp) = test_client_builder(key);
+        let client = builder.build().await.expect("Error creating client");
         (client, tmp)
     }
 
@@ -639,6 +1573,214 @@ mod tests {
         assert_eq!(blocks.next().await.number, 1);
     }
 
+    #[async_std::test]
+    async fn test_runtime_upgrade() {
+        let alice = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id();
+        let (client, _) = test_client().await;
+        let version = client.runtime_version();
+        let mut upgrades = client.on_runtime_upgrade().unwrap();
+        let events = client.subscribe_events().await.unwrap();
+        let decoder = client.events_decoder::<balances::TransferCall<TestRuntime>>();
+        let mut sub = EventSubscription::<TestRuntime>::new(events, decoder);
+        sub.filter_event::<balances::TransferEvent<_>>();
+
+        let set_code = client
+            .encode(system::SetCodeCall {
+                _runtime: PhantomData,
+                code: test_node_runtime_upgrade::WASM_BINARY,
+            })
+            .unwrap();
+        client
+            .sudo_unchecked_weight_and_watch(&alice, &set_code, 0)
+            .await
+            .unwrap();
+        let upgraded = upgrades.next().await.unwrap();
+        assert_eq!(upgraded.spec_version, version.spec_version + 1);
+        assert_eq!(client.runtime_version(), upgraded);
+
+        // extrinsics are signed for the new runtime, and the events of both the
+        // extrinsic and the subscription decoded with its metadata
+        let success = client
+            .transfer_and_watch(&alice, &dest, 10_000)
+            .await
+            .unwrap();
+        let event = success
+            .find_event::<balances::TransferEvent<TestRuntime>>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.amount, 10_000);
+        let raw = sub.next().await.unwrap().unwrap();
+        let event =
+            balances::TransferEvent::<TestRuntime>::decode(&mut &raw.data[..]).unwrap();
+        assert_eq!(event.to, dest);
+        assert_eq!(event.amount, 10_000);
+    }
+
+    #[async_std::test]
+    async fn test_metadata_at() {
+        let (client, _) = test_client().await;
+        let genesis = *client.genesis();
+        let current = client.metadata_at(genesis).await.unwrap();
+        assert!(Arc::ptr_eq(&current, &client.metadata()));
+
+        // pretend the runtime was upgraded since genesis
+        client.state.write().unwrap().runtime_version.spec_version += 1;
+        let previous = client.metadata_at(genesis).await.unwrap();
+        assert!(!Arc::ptr_eq(&previous, &current));
+        assert!(previous.module_with_calls("Balances").is_ok());
+        let cached = client.metadata_at(genesis).await.unwrap();
+        assert!(Arc::ptr_eq(&previous, &cached));
+    }
+
+    #[async_std::test]
+    async fn test_find_call() {
+        let (client, _) = test_client().await;
+        let dest = AccountKeyring::Bob.to_account_id();
+        let transfer = client
+            .encode(balances::TransferCall {
+                to: &dest,
+                amount: 10_000,
+            })
+            .unwrap();
+        let sudo = client
+            .encode(sudo::SudoCall {
+                _runtime: PhantomData,
+                call: &transfer,
+            })
+            .unwrap();
+
+        let proposal = client
+            .find_call::<sudo::OwnedSudoCall<TestRuntime>>(&sudo, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(proposal.call, transfer);
+        let genesis = *client.genesis();
+        assert_eq!(
+            client
+                .find_call::<balances::OwnedTransferCall<TestRuntime>>(
+                    &proposal.call,
+                    Some(genesis)
+                )
+                .await
+                .unwrap(),
+            Some(balances::OwnedTransferCall {
+                to: dest,
+                amount: 10_000,
+            })
+        );
+        assert!(client
+            .find_call::<balances::OwnedTransferCall<TestRuntime>>(&sudo, None)
+            .await
+            .unwrap()
+            .is_none());
+        let mut padded = transfer.clone();
+        padded.0.push(0);
+        assert!(client
+            .find_call::<balances::OwnedTransferCall<TestRuntime>>(&padded, None)
+            .await
+            .is_err());
+    }
+
+    #[async_std::test]
+    async fn test_block_extrinsics() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id();
+        let (client, _) = test_client().await;
+        let success = client
+            .watch(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+        let extrinsics = client
+            .block_extrinsics(success.block)
+            .await
+            .unwrap()
+            .unwrap();
+        let timestamp = extrinsics
+            .iter()
+            .find(|extrinsic| extrinsic.call.module == "Timestamp")
+            .unwrap();
+        assert!(timestamp.extrinsic.signature.is_none());
+        assert_eq!(
+            timestamp
+                .as_call::<balances::OwnedTransferCall<TestRuntime>>()
+                .unwrap(),
+            None
+        );
+        let transfer = extrinsics
+            .iter()
+            .find(|extrinsic| {
+                extrinsic.call.module == "Balances"
+                    && extrinsic.call.function == "transfer"
+            })
+            .unwrap();
+        let (address, _, _) = transfer.extrinsic.signature.as_ref().unwrap();
+        assert_eq!(address, &AccountKeyring::Alice.to_account_id());
+        assert_eq!(
+            transfer
+                .as_call::<balances::OwnedTransferCall<TestRuntime>>()
+                .unwrap(),
+            Some(balances::OwnedTransferCall {
+                to: dest,
+                amount: 10_000,
+            })
+        );
+    }
+
+    #[async_std::test]
+    async fn test_offline_client() {
+        let mut signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id();
+        let (client, tmp) = test_client().await;
+
+        let path = tmp.path().join("metadata.scale");
+        std::fs::write(&path, client.metadata_bytes().await.unwrap()).unwrap();
+        let offline = ClientBuilder::<TestRuntime>::new()
+            .set_metadata(Metadata::from_file(&path).unwrap())
+            .set_genesis_hash(*client.genesis())
+            .set_runtime_version(client.runtime_version())
+            .set_offline()
+            .build()
+            .await
+            .unwrap();
+        assert!(matches!(
+            offline.block_hash(None).await,
+            Err(Error::Offline)
+        ));
+        assert!(matches!(offline.on_runtime_upgrade(), Err(Error::Offline)));
+
+        signer.set_nonce(0);
+        let extrinsic = offline
+            .create_signed(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+        let decoder = client.events_decoder::<balances::TransferCall<TestRuntime>>();
+        let success = client
+            .watch_extrinsic(extrinsic, decoder)
+            .await
+            .unwrap()
+            .wait_for_in_block()
+            .await
+            .unwrap();
+        assert!(success
+            .find_event::<balances::TransferEvent<TestRuntime>>()
+            .unwrap()
+            .is_some());
+    }
+
     #[async_std::test]
     async fn test_tx_transfer_balance() {
         let mut signer = PairSigner::new(AccountKeyring::Alice.pair());
@@ -676,6 +1818,601 @@ mod tests {
             .unwrap();
     }
 
+    #[async_std::test]
+    async fn test_tx_concurrent_transfer_balance() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let (client, _) = test_client().await;
+        let transfers = (0..4).map(|_| {
+            let client = client.clone();
+            let signer = &signer;
+            let dest = &dest;
+            async move {
+                client
+                    .submit(
+                        balances::TransferCall {
+                            to: dest,
+                            amount: 10_000,
+                        },
+                        signer,
+                    )
+                    .await
+            }
+        });
+        let hashes = future::join_all(transfers).await;
+        for hash in hashes {
+            hash.unwrap();
+        }
+    }
+
+    #[async_std::test]
+    async fn test_create_signed_reserves_no_nonce() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id();
+        let (client, _) = test_client().await;
+        let nonce = |extrinsic: UncheckedExtrinsic<TestRuntime>| {
+            let (_, _, extra) = extrinsic.signature.unwrap();
+            (extra.4).0
+        };
+        let transfer = || {
+            balances::TransferCall {
+                to: &dest,
+                amount: 10_000,
+            }
+        };
+
+        // extrinsics which aren't submitted leave no gap in the nonces
+        let unsubmitted = client.create_signed(transfer(), &signer).await.unwrap();
+        client.submit(transfer(), &signer).await.unwrap();
+        let next = client.create_signed(transfer(), &signer).await.unwrap();
+        assert_eq!(nonce(unsubmitted), 0);
+        assert_eq!(nonce(next), 1);
+        client.watch(transfer(), &signer).await.unwrap();
+    }
+
+    #[async_std::test]
+    async fn test_tx_nonce_resync_after_rejection() {
+        let alice = PairSigner::new(AccountKeyring::Alice.pair());
+        let eve = PairSigner::new(AccountKeyring::Eve.pair());
+        let dest = AccountKeyring::Bob.to_account_id();
+        let (builder, _tmp) = test_client_builder(AccountKeyring::Alice);
+        let client = builder
+            .set_watch_timeout(Duration::from_secs(60))
+            .build()
+            .await
+            .unwrap();
+        let transfer = || {
+            balances::TransferCall {
+                to: &dest,
+                amount: 10_000,
+            }
+        };
+
+        // eve can't pay the fee, so her extrinsic never enters the pool
+        let error = client.submit(transfer(), &eve).await.unwrap_err();
+        assert!(error.is_pool_rejection());
+        client
+            .watch(
+                balances::TransferCall {
+                    to: eve.account_id(),
+                    amount: 100_000_000_000,
+                },
+                &alice,
+            )
+            .await
+            .unwrap();
+        // the nonce of the rejected extrinsic is used again instead of leaving a gap
+        client.watch(transfer(), &eve).await.unwrap();
+    }
+
+    #[async_std::test]
+    async fn test_tx_nonce_resync() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let (client, _) = test_client().await;
+        client
+            .submit(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+
+        // use up the next nonce behind the back of the nonce manager
+        let mut other = PairSigner::new(AccountKeyring::Alice.pair());
+        other.set_nonce(1);
+        client
+            .watch(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &other,
+            )
+            .await
+            .unwrap();
+
+        let error = client
+            .submit(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap_err();
+        assert!(error.is_pool_rejection());
+        client
+            .watch(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[async_std::test]
+    async fn test_estimate_fee() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let (client, _) = test_client().await;
+        let info = client
+            .estimate_fee(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+        assert_eq!(info.class, DispatchClass::Normal);
+        assert!(info.weight > 0);
+        assert!(info.partial_fee > 0);
+    }
+
+    #[async_std::test]
+    async fn test_tx_tip() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let alice = AccountKeyring::Alice.to_account_id();
+        let dest = AccountKeyring::Bob.to_account_id().into();
+        let tip = 1_000_000_000_000;
+
+        let (client, _) = test_client().await;
+        let free = || async { client.account(&alice, None).await.unwrap().data.free };
+        let initial = free().await;
+        client
+            .watch(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+        let untipped = free().await;
+
+        let extrinsic = client
+            .create_signed_with_tip(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+                tip,
+            )
+            .await
+            .unwrap();
+        let (_, _, extra) = extrinsic.signature.as_ref().unwrap();
+        assert_eq!((extra.6).0, tip);
+        let decoder = client.events_decoder::<balances::TransferCall<TestRuntime>>();
+        client
+            .submit_and_watch_extrinsic(extrinsic, decoder)
+            .await
+            .unwrap();
+        let tipped = free().await;
+
+        // the tip is charged on top of a fee of about the same size
+        let fee = initial - untipped - 10_000;
+        let fee_and_tip = untipped - tipped - 10_000;
+        assert!(fee_and_tip > tip);
+        assert!(fee_and_tip - tip < 2 * fee);
+    }
+
+    #[async_std::test]
+    async fn test_tx_progress() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let (client, _) = test_client().await;
+        let mut progress = client
+            .watch_progress(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+        assert_eq!(progress.next().await, Some(TransactionStatus::Ready));
+        let success = progress.wait_for_finalized().await.unwrap();
+        let event = success
+            .find_event::<balances::TransferEvent<TestRuntime>>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.amount, 10_000);
+    }
+
+    #[async_std::test]
+    async fn test_tx_concurrent_watch() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let (client, _) = test_client().await;
+        let transfers = (0..4).map(|_| {
+            let client = client.clone();
+            let signer = &signer;
+            let dest = &dest;
+            async move {
+                client
+                    .watch(
+                        balances::TransferCall {
+                            to: dest,
+                            amount: 10_000,
+                        },
+                        signer,
+                    )
+                    .await
+            }
+        });
+        for success in future::join_all(transfers).await {
+            let event = success
+                .unwrap()
+                .find_event::<balances::TransferEvent<TestRuntime>>()
+                .unwrap()
+                .unwrap();
+            assert_eq!(event.amount, 10_000);
+        }
+    }
+
+    #[async_std::test]
+    async fn test_tx_watch_after_inclusion() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let (client, _) = test_client().await;
+        let progress = client
+            .watch_progress(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+        // wait for a few blocks, so the storage change of the inclusion block is long gone
+        let mut blocks = client.subscribe_blocks().await.unwrap();
+        for _ in 0..3 {
+            blocks.next().await;
+        }
+        let success = progress.wait_for_in_block().await.unwrap();
+        assert!(success
+            .find_event::<balances::TransferEvent<TestRuntime>>()
+            .unwrap()
+            .is_some());
+    }
+
+    #[async_std::test]
+    async fn test_tx_watch_timeout() {
+        // Bob is not an authority, so the extrinsic is never included in a block.
+        let (builder, _tmp) = test_client_builder(AccountKeyring::Bob);
+        let client = builder
+            .set_watch_timeout(Duration::from_secs(1))
+            .build()
+            .await
+            .unwrap();
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let result = client
+            .watch(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+    }
+
+    #[async_std::test]
+    async fn test_tx_finalize_timeout() {
+        // Bob is not an authority, so the extrinsic is never included in a block.
+        let (builder, _tmp) = test_client_builder(AccountKeyring::Bob);
+        let client = builder
+            .set_watch_timeout(Duration::from_secs(1))
+            .build()
+            .await
+            .unwrap();
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let result = client
+            .finalize(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+    }
+
+    #[async_std::test]
+    async fn test_tx_mortal_transfer_balance() {
+        let (builder, _tmp) = test_client_builder(AccountKeyring::Alice);
+        let client = builder.set_era_period(4).build().await.unwrap();
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+        client
+            .watch(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[async_std::test]
+    async fn test_tx_mortal_expired() {
+        let (builder, _tmp) = test_client_builder(AccountKeyring::Alice);
+        let client = builder.set_era_period(4).build().await.unwrap();
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let mut blocks = client.subscribe_blocks().await.unwrap();
+        // wait for a block to be produced, so the era doesn't start at genesis.
+        while blocks.next().await.number == 0 {}
+        let xt = client
+            .create_signed(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+        let (_, _, extra) = xt.signature.as_ref().unwrap();
+        let ((era, _), era_hash) = (&(extra.3).0, (extra.3).1);
+        let birth = client.header(Some(era_hash)).await.unwrap().unwrap().number as u64;
+        assert_eq!(era.birth(birth), birth);
+
+        // the extrinsic is valid until the era dies.
+        while (blocks.next().await.number as u64) < era.death(birth) {}
+        // past its era, the birth block is computed anew from the current block,
+        // so the extrinsic is checked against another block hash than it signed.
+        match client.submit_extrinsic(xt).await {
+            Err(Error::Rpc(RequestError::Request(error))) => {
+                assert_eq!(error.code, ErrorCode::ServerError(POOL_INVALID_TX));
+                assert_eq!(error.data, Some(serde_json::json!("BadProof")));
+            }
+            result => panic!("expected an invalid transaction, got {:?}", result),
+        }
+    }
+
+    #[async_std::test]
+    async fn test_system_rpc() {
+        let (client, _) = test_client().await;
+        let alice = AccountKeyring::Alice.to_account_id();
+        assert_eq!(client.system_account_next_index(&alice).await.unwrap(), 0);
+        assert_eq!(client.system_health().await.unwrap().peers, 0);
+        assert!(client.system_peers().await.unwrap().is_empty());
+        assert_eq!(client.system_chain().await.unwrap(), "Development");
+        assert_eq!(
+            client.system_name().await.unwrap(),
+            "substrate-subxt-full-client"
+        );
+        client.system_version().await.unwrap();
+        client.system_properties().await.unwrap();
+        client.system_sync_state().await.unwrap();
+    }
+
+    #[async_std::test]
+    async fn test_events_at() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id().into();
+
+        let (client, _) = test_client().await;
+        let success = client
+            .watch(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+        // wait for the next block, the events must still be available
+        let mut blocks = client.subscribe_blocks().await.unwrap();
+        blocks.next().await;
+
+        let events = client.events_at(success.block).await.unwrap();
+        for (index, record) in events.iter().enumerate() {
+            assert_eq!(record.index as usize, index);
+            assert!(record.topics.is_empty());
+        }
+        let transfer = events.iter().find_map(|record| {
+            match (&record.phase, &record.raw) {
+                (Phase::ApplyExtrinsic(_), Raw::Event(event))
+                    if event.module == "Balances" && event.variant == "Transfer" =>
+                {
+                    Some(event)
+                }
+                _ => None,
+            }
+        });
+        let decoder = client.events_decoder::<balances::TransferCall<TestRuntime>>();
+        let values = decoder.decode_event_values(transfer.unwrap()).unwrap();
+        assert_eq!(
+            values[1],
+            Value::Bytes(AccountKeyring::Bob.to_account_id().as_ref().to_vec())
+        );
+        assert_eq!(values.len(), 3);
+        assert_eq!(
+            client
+                .events_at_with_decoder(success.block, &decoder)
+                .await
+                .unwrap()
+                .len(),
+            events.len()
+        );
+    }
+
+    #[async_std::test]
+    async fn test_metadata() {
+        let (client, _) = test_client().await;
+        let metadata = client.metadata();
+        let transfer = metadata
+            .module_with_calls("Balances")
+            .unwrap()
+            .call_metadata("transfer")
+            .unwrap();
+        assert_eq!(transfer.name(), "transfer");
+        let names = transfer
+            .arguments()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["dest", "value"]);
+        assert!(!transfer.documentation().is_empty());
+
+        let account = metadata
+            .module("System")
+            .unwrap()
+            .storage("Account")
+            .unwrap();
+        assert_eq!(account.name(), "Account");
+        assert_eq!(account.key_types().unwrap().len(), 1);
+        assert!(!account.documentation().is_empty());
+
+        let errors = metadata
+            .modules_with_errors()
+            .find(|module| module.name() == "Balances")
+            .unwrap();
+        assert!(errors.errors().any(|error| {
+            error.name() == "InsufficientBalance" && !error.documentation().is_empty()
+        }));
+    }
+
+    #[async_std::test]
+    async fn test_fetch_dynamic() {
+        let (client, _tmp) = test_client().await;
+        let total_issuance = client.total_issuance(None).await.unwrap();
+        let value = client
+            .fetch_dynamic("Balances", "TotalIssuance", vec![], None)
+            .await
+            .unwrap();
+        assert_eq!(value, Some(Value::Uint(total_issuance)));
+
+        let alice = AccountKeyring::Alice.to_account_id();
+        let info = client.account(&alice, None).await.unwrap();
+        let value = client
+            .fetch_dynamic(
+                "System",
+                "Account",
+                vec![Value::Bytes(alice.as_ref().to_vec())],
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(value, Some(Value::Bytes(info.encode())));
+        assert!(client
+            .fetch_dynamic("System", "Account", vec![], None)
+            .await
+            .is_err());
+    }
+
+    #[async_std::test]
+    async fn test_fetch_dynamic_type_mismatch() {
+        let (builder, _tmp) = test_client_builder(AccountKeyring::Alice);
+        let client = builder
+            .register_type("Balance", TypeDef::Alias(EventArg::Primitive("u64".into())))
+            .build()
+            .await
+            .unwrap();
+        assert!(client
+            .fetch_dynamic("Balances", "TotalIssuance", vec![], None)
+            .await
+            .is_err());
+    }
+
+    #[async_std::test]
+    async fn test_dynamic_call() {
+        let alice = PairSigner::new(AccountKeyring::Alice.pair());
+        let (client, _) = test_client().await;
+        let call = client
+            .dynamic_call(
+                "Balances",
+                "transfer",
+                vec![
+                    Value::Bytes(AccountKeyring::Bob.to_account_id().as_ref().to_vec()),
+                    Value::Uint(10_000),
+                ],
+            )
+            .unwrap();
+        assert!(client
+            .dynamic_call("Balances", "transfer", vec![Value::Uint(10_000)])
+            .is_err());
+        let extrinsic = client
+            .create_signed_dynamic(
+                "Balances",
+                "transfer",
+                vec![
+                    Value::Bytes(AccountKeyring::Bob.to_account_id().as_ref().to_vec()),
+                    Value::Uint(10_000),
+                ],
+                &alice,
+                0,
+            )
+            .await
+            .unwrap();
+        assert_eq!(extrinsic.function, call);
+        let decoder = client.events_decoder::<balances::TransferCall<TestRuntime>>();
+        let success = client
+            .watch_extrinsic(extrinsic, decoder)
+            .await
+            .unwrap()
+            .wait_for_in_block()
+            .await
+            .unwrap();
+        let event = success
+            .find_event::<balances::TransferEvent<TestRuntime>>()
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.to, AccountKeyring::Bob.to_account_id());
+        assert_eq!(event.amount, 10_000);
+    }
+
     #[async_std::test]
     async fn test_getting_hash() {
         let (client, _) = test_client().await;
@@ -719,6 +2456,53 @@ mod tests {
         blocks.next().await;
     }
 
+    #[async_std::test]
+    async fn test_subscribe_finalized_blocks_with_events() {
+        let signer = PairSigner::new(AccountKeyring::Alice.pair());
+        let dest = AccountKeyring::Bob.to_account_id();
+        let (client, _) = test_client().await;
+        let mut blocks = client
+            .subscribe_finalized_blocks_with_events()
+            .await
+            .unwrap();
+        let success = client
+            .watch(
+                balances::TransferCall {
+                    to: &dest,
+                    amount: 10_000,
+                },
+                &signer,
+            )
+            .await
+            .unwrap();
+
+        let mut last: Option<u32> = None;
+        loop {
+            let block = blocks.next().await.unwrap();
+            if let Some(last) = last {
+                assert_eq!(block.header.number, last + 1);
+            }
+            last = Some(block.header.number);
+            if block.hash != success.block {
+                continue
+            }
+            let transfer = block
+                .extrinsics
+                .iter()
+                .find(|xt| xt.extrinsic.call.function == "transfer")
+                .unwrap();
+            assert!(transfer.events.iter().any(|record| {
+                match &record.raw {
+                    Raw::Event(event) => {
+                        event.module == "Balances" && event.variant == "Transfer"
+                    }
+                    Raw::Error(_) => false,
+                }
+            }));
+            break
+        }
+    }
+
     #[async_std::test]
     async fn test_fetch_keys() {
         let (client, _) = test_client().await;
@@ -18,6 +18,7 @@ use std::{
     collections::HashMap,
     convert::TryFrom,
     marker::PhantomData,
+    path::Path,
     str::FromStr,
 };
 
@@ -28,9 +29,17 @@ use codec::{
 };
 
 use frame_metadata::{
+    DFnA,
     DecodeDifferent,
-    RuntimeMetadata,
+    DecodeDifferentArray,
+    DecodeDifferentStr,
+    ErrorMetadata,
+    EventMetadata,
+    FnEncode,
+    FunctionMetadata,
+    ODFnA,
     RuntimeMetadataPrefixed,
+    RuntimeMetadataV11,
     StorageEntryModifier,
     StorageEntryType,
     StorageHasher,
@@ -43,6 +52,9 @@ use crate::Encoded;
 /// Metadata error.
 #[derive(Debug, thiserror::Error)]
 pub enum MetadataError {
+    /// Failed to read metadata.
+    #[error("Error reading metadata: {0}")]
+    Io(#[from] std::io::Error),
     /// Failed to parse metadata.
     #[error("Error converting substrate metadata: {0}")]
     Conversion(#[from] ConversionError),
@@ -54,7 +66,10 @@ pub enum MetadataError {
     ModuleIndexNotFound(u8),
     /// Call is not in metadata.
     #[error("Call {0} not found")]
-    CallNotFound(&'static str),
+    CallNotFound(String),
+    /// Call is not in metadata.
+    #[error("Call index {0} not found")]
+    CallIndexNotFound(u8),
     /// Event is not in metadata.
     #[error("Event {0} not found")]
     EventNotFound(u8),
@@ -63,13 +78,19 @@ pub enum MetadataError {
     ErrorNotFound(u8),
     /// Storage is not in metadata.
     #[error("Storage {0} not found")]
-    StorageNotFound(&'static str),
+    StorageNotFound(String),
+    /// Constant is not in metadata.
+    #[error("Constant {0} not found")]
+    ConstantNotFound(String),
     /// Storage type does not match requested type.
     #[error("Storage type error")]
     StorageTypeError,
     /// Default error.
     #[error("Failed to decode default: {0}")]
     DefaultError(CodecError),
+    /// Constant value error.
+    #[error("Failed to decode constant value: {0}")]
+    ConstantValueError(CodecError),
 }
 
 /// Runtime metadata.
@@ -93,10 +114,18 @@ impl Metadata {
             .ok_or(MetadataError::ModuleNotFound(name))
     }
 
-    pub(crate) fn module_with_calls<S>(
-        &self,
-        name: S,
-    ) -> Result<&ModuleWithCalls, MetadataError>
+    /// Returns the metadata of all modules.
+    pub fn modules(&self) -> impl Iterator<Item = &ModuleMetadata> {
+        self.modules.values()
+    }
+
+    /// Returns the calls of all modules which have any.
+    pub fn modules_with_calls(&self) -> impl Iterator<Item = &ModuleWithCalls> {
+        self.modules_with_calls.values()
+    }
+
+    /// Returns the calls of the module with the given name.
+    pub fn module_with_calls<S>(&self, name: S) -> Result<&ModuleWithCalls, MetadataError>
     where
         S: ToString,
     {
@@ -106,11 +135,32 @@ impl Metadata {
             .ok_or(MetadataError::ModuleNotFound(name))
     }
 
-    pub(crate) fn modules_with_events(&self) -> impl Iterator<Item = &ModuleWithEvents> {
+    /// Returns the module and metadata of the call with the given indices.
+    pub fn call_by_index(
+        &self,
+        module_index: u8,
+        call_index: u8,
+    ) -> Result<(&ModuleWithCalls, &ModuleCallMetadata), MetadataError> {
+        let module = self
+            .modules_with_calls
+            .values()
+            .find(|module| module.index == module_index)
+            .ok_or(MetadataError::ModuleIndexNotFound(module_index))?;
+        let call = module
+            .calls
+            .values()
+            .find(|call| call.index == call_index)
+            .ok_or(MetadataError::CallIndexNotFound(call_index))?;
+        Ok((module, call))
+    }
+
+    /// Returns the events of all modules which have any.
+    pub fn modules_with_events(&self) -> impl Iterator<Item = &ModuleWithEvents> {
         self.modules_with_events.values()
     }
 
-    pub(crate) fn module_with_events(
+    /// Returns the events of the module with the given event index.
+    pub fn module_with_events(
         &self,
         module_index: u8,
     ) -> Result<&ModuleWithEvents, MetadataError> {
@@ -120,7 +170,13 @@ impl Metadata {
             .ok_or(MetadataError::ModuleIndexNotFound(module_index))
     }
 
-    pub(crate) fn module_with_errors(
+    /// Returns the errors of all modules.
+    pub fn modules_with_errors(&self) -> impl Iterator<Item = &ModuleWithErrors> {
+        self.modules_with_errors.values()
+    }
+
+    /// Returns the errors of the module with the given error index.
+    pub fn module_with_errors(
         &self,
         module_index: u8,
     ) -> Result<&ModuleWithErrors, MetadataError> {
@@ -160,43 +216,166 @@ impl Metadata {
     }
 }
 
+/// Metadata of a module.
 #[derive(Clone, Debug)]
 pub struct ModuleMetadata {
+    index: u8,
     name: String,
     storage: HashMap<String, StorageMetadata>,
-    // constants
+    constants: HashMap<String, ModuleConstantMetadata>,
 }
 
 impl ModuleMetadata {
-    pub fn storage(&self, key: &'static str) -> Result<&StorageMetadata, MetadataError> {
+    /// Returns the index of the module in the runtime.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the name of the module.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the storage entries of the module.
+    pub fn storage_entries(&self) -> impl Iterator<Item = &StorageMetadata> {
+        self.storage.values()
+    }
+
+    /// Returns the storage entry with the given name.
+    pub fn storage(&self, key: &str) -> Result<&StorageMetadata, MetadataError> {
         self.storage
             .get(key)
-            .ok_or(MetadataError::StorageNotFound(key))
+            .ok_or_else(|| MetadataError::StorageNotFound(key.to_string()))
+    }
+
+    /// Returns the constants of the module.
+    pub fn constants(&self) -> impl Iterator<Item = &ModuleConstantMetadata> {
+        self.constants.values()
+    }
+
+    /// Returns the constant with the given name.
+    pub fn constant(&self, name: &str) -> Result<&ModuleConstantMetadata, MetadataError> {
+        self.constants
+            .get(name)
+            .ok_or_else(|| MetadataError::ConstantNotFound(name.to_string()))
     }
 }
 
+/// Metadata of a module constant.
+#[derive(Clone, Debug)]
+pub struct ModuleConstantMetadata {
+    name: String,
+    ty: EventArg,
+    value: Vec<u8>,
+    documentation: Vec<String>,
+}
+
+impl ModuleConstantMetadata {
+    /// Returns the name of the constant.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the constant.
+    pub fn ty(&self) -> &EventArg {
+        &self.ty
+    }
+
+    /// Returns the encoded value of the constant.
+    pub fn encoded_value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Decodes the value of the constant.
+    pub fn value<V: Decode>(&self) -> Result<V, MetadataError> {
+        Decode::decode(&mut &self.value[..]).map_err(MetadataError::ConstantValueError)
+    }
+
+    /// Returns the documentation of the constant, one entry per line.
+    pub fn documentation(&self) -> &[String] {
+        &self.documentation
+    }
+}
+
+/// Calls of a module.
 #[derive(Clone, Debug)]
 pub struct ModuleWithCalls {
     index: u8,
-    calls: HashMap<String, u8>,
+    name: String,
+    calls: HashMap<String, ModuleCallMetadata>,
 }
 
 impl ModuleWithCalls {
+    /// Returns the name of the module.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the metadata of all calls of the module.
+    pub fn calls(&self) -> impl Iterator<Item = &ModuleCallMetadata> {
+        self.calls.values()
+    }
+
+    /// Encodes a call of the module with its encoded arguments.
     pub fn call<T: Encode>(
         &self,
         function: &'static str,
         params: T,
     ) -> Result<Encoded, MetadataError> {
-        let fn_index = self
-            .calls
-            .get(function)
-            .ok_or(MetadataError::CallNotFound(function))?;
-        let mut bytes = vec![self.index, *fn_index];
+        let call = self.call_metadata(function)?;
+        let mut bytes = vec![self.index, call.index];
         bytes.extend(params.encode());
         Ok(Encoded(bytes))
     }
+
+    /// Returns the index of the module in calls.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the metadata of the call with the given name.
+    pub fn call_metadata(
+        &self,
+        function: &str,
+    ) -> Result<&ModuleCallMetadata, MetadataError> {
+        self.calls
+            .get(function)
+            .ok_or_else(|| MetadataError::CallNotFound(function.to_string()))
+    }
+}
+
+/// Metadata of a call.
+#[derive(Clone, Debug)]
+pub struct ModuleCallMetadata {
+    index: u8,
+    name: String,
+    arguments: Vec<(String, EventArg)>,
+    documentation: Vec<String>,
+}
+
+impl ModuleCallMetadata {
+    /// Returns the name of the call.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the index of the call within its module.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the names and types of the arguments of the call.
+    pub fn arguments(&self) -> &[(String, EventArg)] {
+        &self.arguments
+    }
+
+    /// Returns the documentation of the call, one entry per line.
+    pub fn documentation(&self) -> &[String] {
+        &self.documentation
+    }
 }
 
+/// Events of a module.
 #[derive(Clone, Debug)]
 pub struct ModuleWithEvents {
     index: u8,
@@ -205,14 +384,22 @@ pub struct ModuleWithEvents {
 }
 
 impl ModuleWithEvents {
+    /// Returns the index of the module in events.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the name of the module.
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Returns the metadata of all events of the module.
     pub fn events(&self) -> impl Iterator<Item = &ModuleEventMetadata> {
         self.events.values()
     }
 
+    /// Returns the metadata of the event with the given index.
     pub fn event(&self, index: u8) -> Result<&ModuleEventMetadata, MetadataError> {
         self.events
             .get(&index)
@@ -220,25 +407,58 @@ impl ModuleWithEvents {
     }
 }
 
+/// Errors of a module.
 #[derive(Clone, Debug)]
 pub struct ModuleWithErrors {
     index: u8,
     name: String,
-    errors: HashMap<u8, String>,
+    errors: HashMap<u8, ModuleErrorMetadata>,
 }
 
 impl ModuleWithErrors {
+    /// Returns the index of the module in errors.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Returns the name of the module.
     pub fn name(&self) -> &str {
         &self.name
     }
 
-    pub fn error(&self, index: u8) -> Result<&String, MetadataError> {
+    /// Returns the metadata of all errors of the module.
+    pub fn errors(&self) -> impl Iterator<Item = &ModuleErrorMetadata> {
+        self.errors.values()
+    }
+
+    /// Returns the metadata of the error with the given index.
+    pub fn error(&self, index: u8) -> Result<&ModuleErrorMetadata, MetadataError> {
         self.errors
             .get(&index)
             .ok_or(MetadataError::ErrorNotFound(index))
     }
 }
 
+/// Metadata of an error.
+#[derive(Clone, Debug)]
+pub struct ModuleErrorMetadata {
+    name: String,
+    documentation: Vec<String>,
+}
+
+impl ModuleErrorMetadata {
+    /// Returns the name of the error.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the documentation of the error, one entry per line.
+    pub fn documentation(&self) -> &[String] {
+        &self.documentation
+    }
+}
+
+/// Metadata of a storage entry.
 #[derive(Clone, Debug)]
 pub struct StorageMetadata {
     module_prefix: String,
@@ -246,19 +466,33 @@ pub struct StorageMetadata {
     modifier: StorageEntryModifier,
     ty: StorageEntryType,
     default: Vec<u8>,
+    documentation: Vec<String>,
 }
 
 impl StorageMetadata {
+    /// Returns the name of the storage entry.
+    pub fn name(&self) -> &str {
+        &self.storage_prefix
+    }
+
+    /// Returns the documentation of the storage entry, one entry per line.
+    pub fn documentation(&self) -> &[String] {
+        &self.documentation
+    }
+
+    /// Returns the key prefix shared by all values of the storage entry.
     pub fn prefix(&self) -> StorageKey {
         let mut bytes = sp_core::twox_128(self.module_prefix.as_bytes()).to_vec();
         bytes.extend(&sp_core::twox_128(self.storage_prefix.as_bytes())[..]);
         StorageKey(bytes)
     }
 
+    /// Decodes the default value of the storage entry.
     pub fn default<V: Decode>(&self) -> Result<V, MetadataError> {
         Decode::decode(&mut &self.default[..]).map_err(MetadataError::DefaultError)
     }
 
+    /// Hashes the encoded key of a map with the given hasher.
     pub fn hash(hasher: &StorageHasher, bytes: &[u8]) -> Vec<u8> {
         match hasher {
             StorageHasher::Identity => bytes.to_vec(),
@@ -284,10 +518,52 @@ impl StorageMetadata {
         }
     }
 
+    /// Encodes and hashes the key of a map with the given hasher.
     pub fn hash_key<K: Encode>(hasher: &StorageHasher, key: &K) -> Vec<u8> {
         Self::hash(hasher, &key.encode())
     }
 
+    /// Returns the hashers and types of the keys of the entry, none for a plain value.
+    pub fn key_types(&self) -> Result<Vec<(StorageHasher, EventArg)>, MetadataError> {
+        Ok(match &self.ty {
+            StorageEntryType::Plain(_) => Vec::new(),
+            StorageEntryType::Map { hasher, key, .. } => {
+                vec![(hasher.clone(), parse_type(&convert(key.clone())?))]
+            }
+            StorageEntryType::DoubleMap {
+                hasher,
+                key1,
+                key2,
+                key2_hasher,
+                ..
+            } => {
+                vec![
+                    (hasher.clone(), parse_type(&convert(key1.clone())?)),
+                    (key2_hasher.clone(), parse_type(&convert(key2.clone())?)),
+                ]
+            }
+        })
+    }
+
+    /// Returns the type of the value stored in the entry.
+    pub fn value_type(&self) -> Result<EventArg, MetadataError> {
+        let value = match &self.ty {
+            StorageEntryType::Plain(value)
+            | StorageEntryType::Map { value, .. }
+            | StorageEntryType::DoubleMap { value, .. } => value,
+        };
+        Ok(parse_type(&convert(value.clone())?))
+    }
+
+    /// Returns the encoded default value, if the entry has one.
+    pub fn default_bytes(&self) -> Option<&[u8]> {
+        match self.modifier {
+            StorageEntryModifier::Default => Some(&self.default),
+            StorageEntryModifier::Optional => None,
+        }
+    }
+
+    /// Returns the key of a plain storage value.
     pub fn plain(&self) -> Result<StoragePlain, MetadataError> {
         match &self.ty {
             StorageEntryType::Plain(_) => {
@@ -299,6 +575,7 @@ impl StorageMetadata {
         }
     }
 
+    /// Returns the key builder of a storage map.
     pub fn map<K: Encode>(&self) -> Result<StorageMap<K>, MetadataError> {
         match &self.ty {
             StorageEntryType::Map { hasher, .. } => {
@@ -312,6 +589,7 @@ impl StorageMetadata {
         }
     }
 
+    /// Returns the key builder of a storage double map.
     pub fn double_map<K1: Encode, K2: Encode>(
         &self,
     ) -> Result<StorageDoubleMap<K1, K2>, MetadataError> {
@@ -376,61 +654,179 @@ impl<K1: Encode, K2: Encode> StorageDoubleMap<K1, K2> {
     }
 }
 
+/// Metadata of an event.
 #[derive(Clone, Debug)]
 pub struct ModuleEventMetadata {
+    /// Name of the event.
     pub name: String,
     arguments: Vec<EventArg>,
+    documentation: Vec<String>,
 }
 
 impl ModuleEventMetadata {
+    /// Returns the types of the arguments of the event.
     pub fn arguments(&self) -> Vec<EventArg> {
         self.arguments.to_vec()
     }
+
+    /// Returns the documentation of the event, one entry per line.
+    pub fn documentation(&self) -> &[String] {
+        &self.documentation
+    }
 }
 
-/// Naive representation of event argument types, supports current set of substrate EventArg types.
+/// Representation of the type of an event argument, parsed from its name in the metadata.
 /// If and when Substrate uses `type-metadata`, this can be replaced.
 ///
-/// Used to calculate the size of a instance of an event variant without having the concrete type,
-/// so the raw bytes can be extracted from the encoded `Vec<EventRecord<E>>` (without `E` defined).
+/// Used to decode an instance of an event variant without having the concrete type, so the
+/// raw bytes can be extracted from the encoded `Vec<EventRecord<E>>` (without `E` defined).
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum EventArg {
+    /// Named type, like `u32` or `AccountId`.
     Primitive(String),
+    /// `Vec<T>`.
     Vec(Box<EventArg>),
+    /// `(A, B, ..)`, `()` being the empty tuple.
     Tuple(Vec<EventArg>),
+    /// `Option<T>`.
+    Option(Box<EventArg>),
+    /// `Compact<T>`.
+    Compact(Box<EventArg>),
+    /// `Result<T, E>`.
+    Result(Box<EventArg>, Box<EventArg>),
+    /// `[T; N]`.
+    Array(Box<EventArg>, usize),
 }
 
 impl FromStr for EventArg {
     type Err = ConversionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.starts_with("Vec<") {
-            if s.ends_with('>') {
-                Ok(EventArg::Vec(Box::new(s[4..s.len() - 1].parse()?)))
-            } else {
-                Err(ConversionError::InvalidEventArg(
+        let mut s = s.trim();
+        if s.starts_with('<') {
+            s = primitive_name(s);
+        }
+        if s.starts_with('(') {
+            if !s.ends_with(')') {
+                return Err(ConversionError::InvalidEventArg(
+                    s.to_string(),
+                    "Expecting closing `)` for tuple",
+                ))
+            }
+            let args = split_args(&s[1..s.len() - 1])
+                .into_iter()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?;
+            return Ok(EventArg::Tuple(args))
+        }
+        if s.starts_with('[') {
+            if !s.ends_with(']') {
+                return Err(ConversionError::InvalidEventArg(
                     s.to_string(),
-                    "Expected closing `>` for `Vec`",
+                    "Expecting closing `]` for array",
                 ))
             }
-        } else if s.starts_with('(') {
-            if s.ends_with(')') {
-                let mut args = Vec::new();
-                for arg in s[1..s.len() - 1].split(',') {
-                    let arg = arg.trim().parse()?;
-                    args.push(arg)
+            let inner = &s[1..s.len() - 1];
+            let (ty, len) = match inner.rfind(';') {
+                Some(pos) => (&inner[..pos], inner[pos + 1..].trim()),
+                None => {
+                    return Err(ConversionError::InvalidEventArg(
+                        s.to_string(),
+                        "Expecting `;` in array",
+                    ))
                 }
-                Ok(EventArg::Tuple(args))
-            } else {
-                Err(ConversionError::InvalidEventArg(
+            };
+            let len = len.parse().map_err(|_| {
+                ConversionError::InvalidEventArg(s.to_string(), "Invalid array length")
+            })?;
+            return Ok(EventArg::Array(Box::new(ty.parse()?), len))
+        }
+        if let Some(start) = s.find('<') {
+            if !s.ends_with('>') {
+                return Err(ConversionError::InvalidEventArg(
                     s.to_string(),
-                    "Expecting closing `)` for tuple",
+                    "Expected closing `>` for generic type",
                 ))
             }
-        } else {
-            Ok(EventArg::Primitive(s.to_string()))
+            let name = primitive_name(&s[..start]);
+            let args = split_args(&s[start + 1..s.len() - 1]);
+            let parse_one = |expected: &'static str| {
+                match args.as_slice() {
+                    [arg] => arg.parse(),
+                    _ => Err(ConversionError::InvalidEventArg(s.to_string(), expected)),
+                }
+            };
+            return match name {
+                "Vec" => {
+                    Ok(EventArg::Vec(Box::new(parse_one(
+                        "Expected one type for `Vec`",
+                    )?)))
+                }
+                "Option" => {
+                    Ok(EventArg::Option(Box::new(parse_one(
+                        "Expected one type for `Option`",
+                    )?)))
+                }
+                "Compact" => {
+                    Ok(EventArg::Compact(Box::new(parse_one(
+                        "Expected one type for `Compact`",
+                    )?)))
+                }
+                "Box" => parse_one("Expected one type for `Box`"),
+                "Result" => {
+                    match args.as_slice() {
+                        [ok, err] => {
+                            Ok(EventArg::Result(
+                                Box::new(ok.parse()?),
+                                Box::new(err.parse()?),
+                            ))
+                        }
+                        _ => {
+                            Err(ConversionError::InvalidEventArg(
+                                s.to_string(),
+                                "Expected two types for `Result`",
+                            ))
+                        }
+                    }
+                }
+                // generic types like `BalanceOf<T>` are looked up by their name
+                _ => Ok(EventArg::Primitive(name.to_string())),
+            }
+        }
+        Ok(EventArg::Primitive(primitive_name(s).to_string()))
+    }
+}
+
+/// Splits the comma separated arguments of a generic type or tuple, ignoring commas of
+/// nested types.
+fn split_args(s: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
         }
     }
+    let last = s[start..].trim();
+    if !last.is_empty() {
+        args.push(last);
+    }
+    args
+}
+
+/// Strips the path of associated types, like in `T::Balance` or `<T as Trait>::Balance`.
+fn primitive_name(s: &str) -> &str {
+    match s.rfind("::") {
+        Some(pos) => &s[pos + 2..],
+        None => s,
+    }
 }
 
 impl EventArg {
@@ -438,7 +834,15 @@ impl EventArg {
     pub fn primitives(&self) -> Vec<String> {
         match self {
             EventArg::Primitive(p) => vec![p.clone()],
-            EventArg::Vec(arg) => arg.primitives(),
+            EventArg::Vec(arg)
+            | EventArg::Option(arg)
+            | EventArg::Compact(arg)
+            | EventArg::Array(arg, _) => arg.primitives(),
+            EventArg::Result(ok, err) => {
+                let mut primitives = ok.primitives();
+                primitives.extend(err.primitives());
+                primitives
+            }
             EventArg::Tuple(args) => {
                 let mut primitives = Vec::new();
                 for arg in args {
@@ -454,31 +858,268 @@ impl EventArg {
 pub enum ConversionError {
     #[error("Invalid prefix")]
     InvalidPrefix,
-    #[error("Invalid version")]
-    InvalidVersion,
+    #[error("Unsupported metadata version {0}")]
+    UnsupportedVersion(u8),
     #[error("Expected DecodeDifferent::Decoded")]
     ExpectedDecoded,
     #[error("Invalid event arg {0}")]
     InvalidEventArg(String, &'static str),
+    #[error("Failed to decode metadata: {0}")]
+    Decode(#[from] CodecError),
 }
 
-impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
-    type Error = MetadataError;
+/// Module metadata of V12, which adds the index of the module to the V11 format.
+#[derive(Decode)]
+struct ModuleMetadataV12 {
+    name: DecodeDifferentStr,
+    storage: Option<
+        DecodeDifferent<
+            FnEncode<frame_metadata::StorageMetadata>,
+            frame_metadata::StorageMetadata,
+        >,
+    >,
+    calls: ODFnA<FunctionMetadata>,
+    event: ODFnA<EventMetadata>,
+    constants: DFnA<frame_metadata::ModuleConstantMetadata>,
+    errors: DFnA<ErrorMetadata>,
+    index: u8,
+}
 
-    fn try_from(metadata: RuntimeMetadataPrefixed) -> Result<Self, Self::Error> {
-        if metadata.0 != META_RESERVED {
-            return Err(ConversionError::InvalidPrefix.into())
+/// Runtime metadata of V12. The extrinsic metadata following the modules is unused.
+#[derive(Decode)]
+struct RuntimeMetadataV12 {
+    modules: DecodeDifferentArray<ModuleMetadataV12>,
+}
+
+/// Storage entry type of V13, which adds maps with any number of keys.
+#[derive(Decode, Encode)]
+enum StorageEntryTypeV13 {
+    Plain(String),
+    Map {
+        hasher: StorageHasher,
+        key: String,
+        value: String,
+        unused: bool,
+    },
+    DoubleMap {
+        hasher: StorageHasher,
+        key1: String,
+        key2: String,
+        value: String,
+        key2_hasher: StorageHasher,
+    },
+    NMap {
+        keys: Vec<String>,
+        hashers: Vec<StorageHasher>,
+        value: String,
+    },
+}
+
+/// Storage entry metadata of V13.
+#[derive(Decode, Encode)]
+struct StorageEntryMetadataV13 {
+    name: String,
+    modifier: StorageEntryModifier,
+    ty: StorageEntryTypeV13,
+    default: Vec<u8>,
+    documentation: Vec<String>,
+}
+
+/// Storage metadata of a module of V13.
+#[derive(Decode, Encode)]
+struct StorageMetadataV13 {
+    prefix: String,
+    entries: Vec<StorageEntryMetadataV13>,
+}
+
+/// Module metadata of V13, which only differs from V12 in its storage entries.
+#[derive(Decode, Encode)]
+struct ModuleMetadataV13 {
+    name: DecodeDifferentStr,
+    storage: Option<StorageMetadataV13>,
+    calls: ODFnA<FunctionMetadata>,
+    event: ODFnA<EventMetadata>,
+    constants: DFnA<frame_metadata::ModuleConstantMetadata>,
+    errors: DFnA<ErrorMetadata>,
+    index: u8,
+}
+
+/// Runtime metadata of V13. The extrinsic metadata following the modules is unused.
+#[derive(Decode)]
+struct RuntimeMetadataV13 {
+    modules: Vec<ModuleMetadataV13>,
+}
+
+/// Module metadata in the V11 format, along with the index of the module if the
+/// metadata version states it explicitly.
+type VersionedModule = (frame_metadata::ModuleMetadata, Option<u8>);
+
+/// Decodes the modules of metadata of the given version, converting them to the
+/// V11 format.
+fn versioned_modules(
+    version: u8,
+    input: &mut &[u8],
+) -> Result<Vec<VersionedModule>, ConversionError> {
+    match version {
+        11 => versioned_v11(RuntimeMetadataV11::decode(input)?),
+        12 => {
+            convert(RuntimeMetadataV12::decode(input)?.modules)?
+                .into_iter()
+                .map(|module| {
+                    let index = module.index;
+                    let module = frame_metadata::ModuleMetadata {
+                        name: module.name,
+                        storage: module.storage,
+                        calls: module.calls,
+                        event: module.event,
+                        constants: module.constants,
+                        errors: module.errors,
+                    };
+                    Ok((module, Some(index)))
+                })
+                .collect()
+        }
+        13 => {
+            RuntimeMetadataV13::decode(input)?
+                .modules
+                .into_iter()
+                .map(|module| {
+                    let storage = module.storage.map(|storage| {
+                        DecodeDifferent::Decoded(frame_metadata::StorageMetadata {
+                            prefix: DecodeDifferent::Decoded(storage.prefix),
+                            entries: DecodeDifferent::Decoded(
+                                storage
+                                    .entries
+                                    .into_iter()
+                                    .filter_map(StorageEntryMetadataV13::into_v11)
+                                    .collect(),
+                            ),
+                        })
+                    });
+                    let index = module.index;
+                    let module = frame_metadata::ModuleMetadata {
+                        name: module.name,
+                        storage,
+                        calls: module.calls,
+                        event: module.event,
+                        constants: module.constants,
+                        errors: module.errors,
+                    };
+                    Ok((module, Some(index)))
+                })
+                .collect()
         }
-        let meta = match metadata.1 {
-            RuntimeMetadata::V11(meta) => meta,
-            _ => return Err(ConversionError::InvalidVersion.into()),
+        version => Err(ConversionError::UnsupportedVersion(version)),
+    }
+}
+
+impl StorageEntryMetadataV13 {
+    /// Converts the entry to the V11 format, which can't express maps with more than
+    /// two keys. Such entries are skipped.
+    fn into_v11(self) -> Option<frame_metadata::StorageEntryMetadata> {
+        let ty = match self.ty {
+            StorageEntryTypeV13::Plain(value) => {
+                StorageEntryType::Plain(DecodeDifferent::Decoded(value))
+            }
+            StorageEntryTypeV13::Map {
+                hasher,
+                key,
+                value,
+                unused,
+            } => {
+                StorageEntryType::Map {
+                    hasher,
+                    key: DecodeDifferent::Decoded(key),
+                    value: DecodeDifferent::Decoded(value),
+                    unused,
+                }
+            }
+            StorageEntryTypeV13::DoubleMap {
+                hasher,
+                key1,
+                key2,
+                value,
+                key2_hasher,
+            } => {
+                StorageEntryType::DoubleMap {
+                    hasher,
+                    key1: DecodeDifferent::Decoded(key1),
+                    key2: DecodeDifferent::Decoded(key2),
+                    value: DecodeDifferent::Decoded(value),
+                    key2_hasher,
+                }
+            }
+            StorageEntryTypeV13::NMap {
+                keys,
+                hashers,
+                value,
+            } => {
+                match (&keys[..], &hashers[..]) {
+                    ([key], [hasher]) => {
+                        StorageEntryType::Map {
+                            hasher: hasher.clone(),
+                            key: DecodeDifferent::Decoded(key.clone()),
+                            value: DecodeDifferent::Decoded(value),
+                            unused: false,
+                        }
+                    }
+                    ([key1, key2], [hasher, key2_hasher]) => {
+                        StorageEntryType::DoubleMap {
+                            hasher: hasher.clone(),
+                            key1: DecodeDifferent::Decoded(key1.clone()),
+                            key2: DecodeDifferent::Decoded(key2.clone()),
+                            value: DecodeDifferent::Decoded(value),
+                            key2_hasher: key2_hasher.clone(),
+                        }
+                    }
+                    _ => {
+                        log::warn!(
+                            "Skipping storage {} with {} keys",
+                            self.name,
+                            keys.len()
+                        );
+                        return None
+                    }
+                }
+            }
         };
+        Some(frame_metadata::StorageEntryMetadata {
+            name: DecodeDifferent::Decoded(self.name),
+            modifier: self.modifier,
+            ty,
+            default: DecodeDifferent::Decoded(self.default),
+            documentation: DecodeDifferent::Decoded(self.documentation),
+        })
+    }
+}
+
+impl Metadata {
+    /// Decodes metadata of any supported version, prefixed with the magic number and
+    /// the version as returned by `state_getMetadata`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MetadataError> {
+        let input = &mut &bytes[..];
+        if u32::decode(input).map_err(ConversionError::from)? != META_RESERVED {
+            return Err(ConversionError::InvalidPrefix.into())
+        }
+        let version = u8::decode(input).map_err(ConversionError::from)?;
+        Self::from_modules(versioned_modules(version, input)?)
+    }
+
+    /// Reads metadata from a file containing the bytes returned by `state_getMetadata`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, MetadataError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    fn from_modules(versioned: Vec<VersionedModule>) -> Result<Self, MetadataError> {
         let mut modules = HashMap::new();
         let mut modules_with_calls = HashMap::new();
         let mut modules_with_events = HashMap::new();
         let mut modules_with_errors = HashMap::new();
-        for module in convert(meta.modules)?.into_iter() {
+        for (position, (module, explicit_index)) in versioned.into_iter().enumerate() {
             let module_name = convert(module.name.clone())?;
+            // without explicit indices, the index of a module is its position, except
+            // for calls and events which only count the modules having any
+            let index = explicit_index.unwrap_or(position as u8);
 
             let mut storage_map = HashMap::new();
             if let Some(storage) = module.storage {
@@ -494,11 +1135,18 @@ impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
                     storage_map.insert(storage_prefix, entry);
                 }
             }
+            let mut constant_map = HashMap::new();
+            for constant in convert(module.constants)?.into_iter() {
+                let constant = convert_constant(constant)?;
+                constant_map.insert(constant.name.clone(), constant);
+            }
             modules.insert(
                 module_name.clone(),
                 ModuleMetadata {
+                    index,
                     name: module_name.clone(),
                     storage: storage_map,
+                    constants: constant_map,
                 },
             );
 
@@ -506,12 +1154,26 @@ impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
                 let mut call_map = HashMap::new();
                 for (index, call) in convert(calls)?.into_iter().enumerate() {
                     let name = convert(call.name)?;
-                    call_map.insert(name, index as u8);
+                    let mut arguments = Vec::new();
+                    for arg in convert(call.arguments)? {
+                        let ty = convert(arg.ty)?;
+                        arguments.push((convert(arg.name)?, parse_type(&ty)));
+                    }
+                    call_map.insert(
+                        name.clone(),
+                        ModuleCallMetadata {
+                            index: index as u8,
+                            name,
+                            arguments,
+                            documentation: convert(call.documentation)?,
+                        },
+                    );
                 }
                 modules_with_calls.insert(
                     module_name.clone(),
                     ModuleWithCalls {
-                        index: modules_with_calls.len() as u8,
+                        index: explicit_index.unwrap_or(modules_with_calls.len() as u8),
+                        name: module_name.clone(),
                         calls: call_map,
                     },
                 );
@@ -524,7 +1186,7 @@ impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
                 modules_with_events.insert(
                     module_name.clone(),
                     ModuleWithEvents {
-                        index: modules_with_events.len() as u8,
+                        index: explicit_index.unwrap_or(modules_with_events.len() as u8),
                         name: module_name.clone(),
                         events: event_map,
                     },
@@ -537,7 +1199,7 @@ impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
             modules_with_errors.insert(
                 module_name.clone(),
                 ModuleWithErrors {
-                    index: modules_with_errors.len() as u8,
+                    index,
                     name: module_name.clone(),
                     errors: error_map,
                 },
@@ -552,6 +1214,26 @@ impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
     }
 }
 
+impl TryFrom<RuntimeMetadataPrefixed> for Metadata {
+    type Error = MetadataError;
+
+    /// Converts the metadata like `from_bytes`, which supports more versions than
+    /// `RuntimeMetadataPrefixed` can represent.
+    fn try_from(metadata: RuntimeMetadataPrefixed) -> Result<Self, Self::Error> {
+        Self::from_bytes(&metadata.encode())
+    }
+}
+
+/// V11 doesn't state module indices, which are implied by the order of the modules.
+fn versioned_v11(
+    meta: RuntimeMetadataV11,
+) -> Result<Vec<VersionedModule>, ConversionError> {
+    Ok(convert(meta.modules)?
+        .into_iter()
+        .map(|module| (module, None))
+        .collect())
+}
+
 fn convert<B: 'static, O: 'static>(
     dd: DecodeDifferent<B, O>,
 ) -> Result<O, ConversionError> {
@@ -561,6 +1243,13 @@ fn convert<B: 'static, O: 'static>(
     }
 }
 
+/// Parses a type name, keeping types which aren't understood as primitives, whose
+/// values can still be handled as encoded bytes.
+fn parse_type(ty: &str) -> EventArg {
+    ty.parse()
+        .unwrap_or_else(|_| EventArg::Primitive(ty.to_string()))
+}
+
 fn convert_event(
     event: frame_metadata::EventMetadata,
 ) -> Result<ModuleEventMetadata, ConversionError> {
@@ -570,7 +1259,11 @@ fn convert_event(
         let arg = arg.parse::<EventArg>()?;
         arguments.push(arg);
     }
-    Ok(ModuleEventMetadata { name, arguments })
+    Ok(ModuleEventMetadata {
+        name,
+        arguments,
+        documentation: convert(event.documentation)?,
+    })
 }
 
 fn convert_entry(
@@ -585,11 +1278,466 @@ fn convert_entry(
         modifier: entry.modifier,
         ty: entry.ty,
         default,
+        documentation: convert(entry.documentation)?,
+    })
+}
+
+fn convert_constant(
+    constant: frame_metadata::ModuleConstantMetadata,
+) -> Result<ModuleConstantMetadata, ConversionError> {
+    Ok(ModuleConstantMetadata {
+        name: convert(constant.name)?,
+        ty: parse_type(&convert(constant.ty)?),
+        value: convert(constant.value)?,
+        documentation: convert(constant.documentation)?,
     })
 }
 
 fn convert_error(
     error: frame_metadata::ErrorMetadata,
-) -> Result<String, ConversionError> {
-    convert(error.name)
+) -> Result<ModuleErrorMetadata, ConversionError> {
+    Ok(ModuleErrorMetadata {
+        name: convert(error.name)?,
+        documentation: convert(error.documentation)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        events::{
+            EventsDecoder,
+            Raw,
+        },
+        frame::balances::{
+            BalancesEventsDecoder,
+            TransferCall,
+            TransferEvent,
+        },
+        tests::TestRuntime,
+        ModuleError,
+        RuntimeError,
+    };
+    use codec::Compact;
+    use frame_metadata::{
+        ExtrinsicMetadata,
+        RuntimeMetadata,
+    };
+    use frame_support::weights::DispatchInfo;
+    use frame_system::Phase;
+    use sp_core::H256;
+    use sp_keyring::AccountKeyring;
+    use sp_runtime::DispatchError;
+
+    fn named(name: &str) -> EventArg {
+        EventArg::Primitive(name.to_string())
+    }
+
+    #[test]
+    fn test_parse_event_arg() {
+        let arg: EventArg = "Vec<(T::AccountId, Option<[u8; 4]>)>".parse().unwrap();
+        assert_eq!(
+            arg,
+            EventArg::Vec(Box::new(EventArg::Tuple(vec![
+                named("AccountId"),
+                EventArg::Option(Box::new(EventArg::Array(Box::new(named("u8")), 4))),
+            ])))
+        );
+        let arg: EventArg = "Result<(), <T as Trait>::Error>".parse().unwrap();
+        assert_eq!(
+            arg,
+            EventArg::Result(Box::new(EventArg::Tuple(vec![])), Box::new(named("Error")))
+        );
+        let arg: EventArg = "Compact<BalanceOf<T>>".parse().unwrap();
+        assert_eq!(arg, EventArg::Compact(Box::new(named("BalanceOf"))));
+    }
+
+    fn module(name: &str, calls: &[&str]) -> frame_metadata::ModuleMetadata {
+        module_with_events(name, calls, &[], &[])
+    }
+
+    fn module_with_events(
+        name: &str,
+        calls: &[&str],
+        events: &[&str],
+        errors: &[&str],
+    ) -> frame_metadata::ModuleMetadata {
+        let calls = calls
+            .iter()
+            .map(|call| {
+                FunctionMetadata {
+                    name: DecodeDifferent::Decoded(call.to_string()),
+                    arguments: DecodeDifferent::Decoded(vec![]),
+                    documentation: DecodeDifferent::Decoded(vec![]),
+                }
+            })
+            .collect::<Vec<_>>();
+        let events = events
+            .iter()
+            .map(|event| {
+                EventMetadata {
+                    name: DecodeDifferent::Decoded(event.to_string()),
+                    arguments: DecodeDifferent::Decoded(vec![]),
+                    documentation: DecodeDifferent::Decoded(vec![]),
+                }
+            })
+            .collect::<Vec<_>>();
+        let errors = errors
+            .iter()
+            .map(|error| {
+                ErrorMetadata {
+                    name: DecodeDifferent::Decoded(error.to_string()),
+                    documentation: DecodeDifferent::Decoded(vec![]),
+                }
+            })
+            .collect::<Vec<_>>();
+        frame_metadata::ModuleMetadata {
+            name: DecodeDifferent::Decoded(name.to_string()),
+            storage: None,
+            calls: if calls.is_empty() {
+                None
+            } else {
+                Some(DecodeDifferent::Decoded(calls))
+            },
+            event: if events.is_empty() {
+                None
+            } else {
+                Some(DecodeDifferent::Decoded(events))
+            },
+            constants: DecodeDifferent::Decoded(vec![]),
+            errors: DecodeDifferent::Decoded(errors),
+        }
+    }
+
+    fn extrinsic() -> ExtrinsicMetadata {
+        ExtrinsicMetadata {
+            version: 4,
+            signed_extensions: vec![],
+        }
+    }
+
+    fn encode_v11(modules: Vec<frame_metadata::ModuleMetadata>) -> Vec<u8> {
+        RuntimeMetadataPrefixed(
+            META_RESERVED,
+            RuntimeMetadata::V11(RuntimeMetadataV11 {
+                modules: DecodeDifferent::Decoded(modules),
+                extrinsic: extrinsic(),
+            }),
+        )
+        .encode()
+    }
+
+    /// V12 modules are encoded like V11 ones, followed by their index.
+    fn encode_v12(modules: Vec<(frame_metadata::ModuleMetadata, u8)>) -> Vec<u8> {
+        let mut bytes = META_RESERVED.encode();
+        bytes.push(12);
+        Compact(modules.len() as u32).encode_to(&mut bytes);
+        for (module, index) in modules {
+            module.encode_to(&mut bytes);
+            bytes.push(index);
+        }
+        extrinsic().encode_to(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    fn test_decode_v11() {
+        let bytes = encode_v11(vec![
+            module("System", &["remark"]),
+            module("Timestamp", &[]),
+            module("Balances", &["transfer"]),
+        ]);
+        let metadata = Metadata::from_bytes(&bytes).unwrap();
+        assert!(metadata.module("Timestamp").is_ok());
+        let balances = metadata.module_with_calls("Balances").unwrap();
+        assert_eq!(balances.index(), 1);
+        assert_eq!(balances.call_metadata("transfer").unwrap().index(), 0);
+    }
+
+    #[test]
+    fn test_decode_v12() {
+        let bytes = encode_v12(vec![
+            (module("System", &["remark"]), 0),
+            (module("Timestamp", &[]), 1),
+            (module("Balances", &["transfer"]), 2),
+        ]);
+        let metadata = Metadata::from_bytes(&bytes).unwrap();
+        assert!(metadata.module("Timestamp").is_ok());
+        let balances = metadata.module_with_calls("Balances").unwrap();
+        assert_eq!(balances.index(), 2);
+        assert_eq!(balances.call_metadata("transfer").unwrap().index(), 0);
+    }
+
+    #[test]
+    fn test_implicit_indices() {
+        let bytes = encode_v11(vec![
+            module_with_events("System", &["remark"], &["ExtrinsicSuccess"], &[]),
+            module_with_events("Timestamp", &["set"], &[], &[]),
+            module_with_events(
+                "Balances",
+                &["transfer"],
+                &["Transfer"],
+                &["VestingBalance"],
+            ),
+        ]);
+        let metadata = Metadata::from_bytes(&bytes).unwrap();
+        assert_eq!(metadata.module("Balances").unwrap().index(), 2);
+        assert_eq!(metadata.module_with_calls("Balances").unwrap().index(), 2);
+        assert_eq!(metadata.module_with_events(1).unwrap().name(), "Balances");
+        assert_eq!(metadata.module_with_errors(2).unwrap().name(), "Balances");
+    }
+
+    #[test]
+    fn test_explicit_indices() {
+        // the modules are out of order, with gaps between their indices
+        let bytes = encode_v12(vec![
+            (
+                module_with_events(
+                    "Balances",
+                    &["transfer"],
+                    &["Transfer"],
+                    &["VestingBalance"],
+                ),
+                5,
+            ),
+            (module("Timestamp", &[]), 3),
+            (
+                module_with_events("System", &["remark"], &["ExtrinsicSuccess"], &[]),
+                0,
+            ),
+        ]);
+        let metadata = Metadata::from_bytes(&bytes).unwrap();
+        assert_eq!(metadata.module("Balances").unwrap().index(), 5);
+        assert_eq!(metadata.module("Timestamp").unwrap().index(), 3);
+        assert_eq!(metadata.module_with_calls("Balances").unwrap().index(), 5);
+        assert_eq!(metadata.module_with_calls("System").unwrap().index(), 0);
+        assert_eq!(metadata.module_with_events(5).unwrap().name(), "Balances");
+        assert_eq!(metadata.module_with_events(0).unwrap().name(), "System");
+        assert!(metadata.module_with_events(1).is_err());
+        let errors = metadata.module_with_errors(5).unwrap();
+        assert_eq!(errors.name(), "Balances");
+        assert_eq!(errors.error(0).unwrap().name(), "VestingBalance");
+
+        let call = metadata
+            .module_with_calls("Balances")
+            .unwrap()
+            .call("transfer", ())
+            .unwrap();
+        assert_eq!(call.0, vec![5, 0]);
+    }
+
+    #[test]
+    fn test_explicit_indices_of_runtime() {
+        // the metadata of the test-node runtime, listed in reverse order with gaps
+        // between the indices of its modules
+        let prefixed = RuntimeMetadataPrefixed::decode(
+            &mut &test_node_runtime::Runtime::metadata().encode()[..],
+        )
+        .unwrap();
+        let modules = match prefixed.1 {
+            RuntimeMetadata::V11(metadata) => convert(metadata.modules).unwrap(),
+            _ => panic!("test-node runtime has no V11 metadata"),
+        };
+        let index = |name: &str| {
+            match name {
+                "System" => 0,
+                "Timestamp" => 3,
+                "Sudo" => 9,
+                "Balances" => 12,
+                "RandomnessCollectiveFlip" => 20,
+                "Aura" => 21,
+                "Grandpa" => 22,
+                "TransactionPayment" => 23,
+                _ => panic!("unexpected module {}", name),
+            }
+        };
+        let find = |name: &str| {
+            modules
+                .iter()
+                .find(|module| convert(module.name.clone()).unwrap() == name)
+                .unwrap()
+                .clone()
+        };
+        let position = |names: Vec<String>, name: &str| {
+            names.iter().position(|n| n == name).unwrap() as u8
+        };
+        let reindexed = modules
+            .iter()
+            .rev()
+            .map(|module| {
+                let name = convert(module.name.clone()).unwrap();
+                (module.clone(), index(&name))
+            })
+            .collect();
+        let metadata = Metadata::from_bytes(&encode_v12(reindexed)).unwrap();
+        assert_eq!(metadata.module("Balances").unwrap().index(), 12);
+
+        let bob = AccountKeyring::Bob.to_account_id();
+        let call = metadata
+            .module_with_calls("Balances")
+            .unwrap()
+            .call(
+                "transfer",
+                TransferCall::<TestRuntime> {
+                    to: &bob.clone().into(),
+                    amount: 10_000,
+                },
+            )
+            .unwrap();
+        assert_eq!(call.0[..2], [12, 0]);
+
+        let balances = find("Balances");
+        let event_names: Vec<String> = convert(balances.event.unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|event| convert(event.name).unwrap())
+            .collect();
+        let error_names: Vec<String> = convert(balances.errors)
+            .unwrap()
+            .into_iter()
+            .map(|error| convert(error.name).unwrap())
+            .collect();
+        let system_events: Vec<String> = convert(find("System").event.unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|event| convert(event.name).unwrap())
+            .collect();
+
+        let alice = AccountKeyring::Alice.to_account_id();
+        let mut events = Compact(2u32).encode();
+        Phase::ApplyExtrinsic(1).encode_to(&mut events);
+        events.extend(&[12, position(event_names, "Transfer")]);
+        (&alice, &bob, 10_000u128).encode_to(&mut events);
+        Vec::<H256>::new().encode_to(&mut events);
+        Phase::ApplyExtrinsic(2).encode_to(&mut events);
+        events.extend(&[0, position(system_events, "ExtrinsicFailed")]);
+        DispatchError::Module {
+            index: 12,
+            error: position(error_names, "InsufficientBalance"),
+            message: None,
+        }
+        .encode_to(&mut events);
+        DispatchInfo::default().encode_to(&mut events);
+        Vec::<H256>::new().encode_to(&mut events);
+
+        let mut decoder = EventsDecoder::<TestRuntime>::new(metadata);
+        decoder.with_balances();
+        let records = decoder.decode_event_records(&mut &events[..]).unwrap();
+        assert_eq!(records.len(), 2);
+        match &records[0].raw {
+            Raw::Event(event) => {
+                assert_eq!(event.module, "Balances");
+                assert_eq!(event.variant, "Transfer");
+                let transfer =
+                    TransferEvent::<TestRuntime>::decode(&mut &event.data[..]).unwrap();
+                assert_eq!(transfer.from, alice);
+                assert_eq!(transfer.to, bob);
+                assert_eq!(transfer.amount, 10_000);
+            }
+            raw => panic!("expected the transfer event, got {:?}", raw),
+        }
+        match &records[1].raw {
+            Raw::Error(error) => {
+                assert_eq!(
+                    error,
+                    &RuntimeError::Module(ModuleError {
+                        module: "Balances".into(),
+                        error: "InsufficientBalance".into(),
+                    })
+                );
+            }
+            raw => panic!("expected the balances error, got {:?}", raw),
+        }
+    }
+
+    #[test]
+    fn test_decode_v13() {
+        let entry = |name: &str, keys: &[&str]| {
+            StorageEntryMetadataV13 {
+                name: name.to_string(),
+                modifier: StorageEntryModifier::Optional,
+                ty: StorageEntryTypeV13::NMap {
+                    keys: keys.iter().map(|key| key.to_string()).collect(),
+                    hashers: keys.iter().map(|_| StorageHasher::Twox64Concat).collect(),
+                    value: "u32".to_string(),
+                },
+                default: vec![0],
+                documentation: vec![],
+            }
+        };
+        let balances = module("Balances", &["transfer"]);
+        let module = ModuleMetadataV13 {
+            name: balances.name,
+            storage: Some(StorageMetadataV13 {
+                prefix: "Balances".to_string(),
+                entries: vec![
+                    entry("Map", &["u32"]),
+                    entry("DoubleMap", &["u32", "u64"]),
+                    entry("TripleMap", &["u32", "u64", "u8"]),
+                ],
+            }),
+            calls: balances.calls,
+            event: balances.event,
+            constants: balances.constants,
+            errors: balances.errors,
+            index: 4,
+        };
+        let mut bytes = META_RESERVED.encode();
+        bytes.push(13);
+        vec![module].encode_to(&mut bytes);
+        extrinsic().encode_to(&mut bytes);
+
+        let metadata = Metadata::from_bytes(&bytes).unwrap();
+        let balances = metadata.module("Balances").unwrap();
+        assert_eq!(balances.index(), 4);
+        assert_eq!(
+            balances.storage("Map").unwrap().key_types().unwrap().len(),
+            1
+        );
+        assert_eq!(
+            balances
+                .storage("DoubleMap")
+                .unwrap()
+                .key_types()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(balances.storage("TripleMap").is_err());
+        assert_eq!(
+            metadata
+                .module_with_calls("Balances")
+                .unwrap()
+                .call("transfer", ())
+                .unwrap()
+                .0,
+            vec![4, 0]
+        );
+    }
+
+    #[test]
+    fn test_try_from_prefixed() {
+        let metadata = RuntimeMetadataPrefixed(
+            META_RESERVED,
+            RuntimeMetadata::V11(RuntimeMetadataV11 {
+                modules: DecodeDifferent::Decoded(vec![module("Balances", &[])]),
+                extrinsic: extrinsic(),
+            }),
+        );
+        let metadata = Metadata::try_from(metadata).unwrap();
+        assert!(metadata.module("Balances").is_ok());
+    }
+
+    #[test]
+    fn test_decode_unsupported_version() {
+        let mut bytes = META_RESERVED.encode();
+        bytes.push(14);
+        assert!(matches!(
+            Metadata::from_bytes(&bytes),
+            Err(MetadataError::Conversion(
+                ConversionError::UnsupportedVersion(14)
+            ))
+        ));
+    }
 }
@@ -0,0 +1,94 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of substrate-subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with substrate-subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Hands out account nonces to concurrent submissions.
+
+use futures::lock::Mutex;
+use sp_runtime::traits::One;
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+};
+
+use crate::{
+    error::Error,
+    frame::system::System,
+    rpc::Rpc,
+    runtimes::Runtime,
+};
+
+/// Keeps track of the next nonce of every account that submitted through a
+/// `Client`. Clones share the same state.
+pub(crate) struct NonceManager<T: System> {
+    nonces: Arc<Mutex<BTreeMap<T::AccountId, T::Index>>>,
+}
+
+impl<T: System> Clone for NonceManager<T> {
+    fn clone(&self) -> Self {
+        Self {
+            nonces: self.nonces.clone(),
+        }
+    }
+}
+
+impl<T: System> Default for NonceManager<T> {
+    fn default() -> Self {
+        Self {
+            nonces: Default::default(),
+        }
+    }
+}
+
+impl<T: Runtime> NonceManager<T> {
+    /// Returns the nonce to use for the next extrinsic of `account`.
+    ///
+    /// The first nonce of an account is fetched with `system_accountNextIndex`,
+    /// subsequent nonces are handed out locally.
+    pub async fn next(
+        &self,
+        rpc: &Rpc<T>,
+        account: &T::AccountId,
+    ) -> Result<T::Index, Error> {
+        let mut nonces = self.nonces.lock().await;
+        let nonce = if let Some(nonce) = nonces.get(account) {
+            *nonce
+        } else {
+            rpc.system_account_next_index(account).await?
+        };
+        nonces.insert(account.clone(), nonce + One::one());
+        Ok(nonce)
+    }
+
+    /// Returns the nonce the next extrinsic of `account` would get, without
+    /// reserving it.
+    pub async fn peek(
+        &self,
+        rpc: &Rpc<T>,
+        account: &T::AccountId,
+    ) -> Result<T::Index, Error> {
+        let nonce = self.nonces.lock().await.get(account).copied();
+        match nonce {
+            Some(nonce) => Ok(nonce),
+            None => rpc.system_account_next_index(account).await,
+        }
+    }
+
+    /// Forgets the nonce of `account`, so that the next one is fetched from the
+    /// node again.
+    pub async fn reset(&self, account: &T::AccountId) {
+        self.nonces.lock().await.remove(account);
+    }
+}
@@ -24,11 +24,11 @@ use codec::{
     Encode,
     Error as CodecError,
 };
-use core::{
-    convert::TryInto,
-    marker::PhantomData,
+use core::marker::PhantomData;
+use frame_support::weights::{
+    DispatchClass,
+    Weight,
 };
-use frame_metadata::RuntimeMetadataPrefixed;
 use jsonrpsee::{
     client::Subscription,
     common::{
@@ -38,7 +38,14 @@ use jsonrpsee::{
     Client,
 };
 use sc_rpc_api::state::ReadProof;
-use serde::Serialize;
+pub use sc_rpc_api::system::{
+    Health,
+    PeerInfo,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use sp_core::{
     storage::{
         StorageChangeSet,
@@ -58,6 +65,7 @@ use sp_runtime::{
         SignedBlock,
     },
     traits::Hash,
+    ApplyExtrinsicResult,
 };
 use sp_transaction_pool::TransactionStatus;
 use sp_version::RuntimeVersion;
@@ -65,16 +73,21 @@ use sp_version::RuntimeVersion;
 use crate::{
     error::Error,
     events::{
+        EventRecord,
         EventsDecoder,
+        Raw,
         RawEvent,
     },
     frame::{
-        system::System,
+        balances::Balances,
+        system::{
+            Phase,
+            System,
+        },
         Event,
     },
     metadata::Metadata,
     runtimes::Runtime,
-    subscription::EventSubscription,
 };
 
 pub type ChainBlock<T> =
@@ -96,9 +109,51 @@ impl From<u32> for BlockNumber {
     }
 }
 
+impl From<u64> for BlockNumber {
+    fn from(x: u64) -> Self {
+        NumberOrHex::Number(x.into()).into()
+    }
+}
+
+/// Chain properties reported by `system_properties`.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemProperties {
+    /// Address format of the chain.
+    pub ss58_format: Option<u8>,
+    /// Number of decimals of the native token.
+    pub token_decimals: Option<u8>,
+    /// Symbol of the native token.
+    pub token_symbol: Option<String>,
+}
+
+/// Block synchronization progress reported by `system_syncState`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState<N> {
+    /// Height of the block at which syncing started.
+    pub starting_block: N,
+    /// Height of the current best block of the node.
+    pub current_block: N,
+    /// Height of the highest block learned from the network, if any.
+    pub highest_block: Option<N>,
+}
+
+/// Information about the dispatch of an extrinsic, as returned by `payment_queryInfo`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeDispatchInfo<Balance> {
+    /// Weight of the extrinsic.
+    pub weight: Weight,
+    /// Dispatch class of the extrinsic.
+    pub class: DispatchClass,
+    /// Inclusion fee of the extrinsic, excluding the tip.
+    pub partial_fee: Balance,
+}
+
 /// Client for substrate rpc interfaces
 pub struct Rpc<T: Runtime> {
-    client: Client,
+    client: Option<Client>,
     marker: PhantomData<T>,
 }
 
@@ -114,11 +169,24 @@ impl<T: Runtime> Clone for Rpc<T> {
 impl<T: Runtime> Rpc<T> {
     pub fn new(client: Client) -> Self {
         Self {
-            client,
+            client: Some(client),
             marker: PhantomData,
         }
     }
 
+    /// Creates an rpc client without a node, whose requests fail with
+    /// `Error::Offline`.
+    pub fn offline() -> Self {
+        Self {
+            client: None,
+            marker: PhantomData,
+        }
+    }
+
+    fn client(&self) -> Result<&Client, Error> {
+        self.client.as_ref().ok_or(Error::Offline)
+    }
+
     /// Fetch a storage key
     pub async fn storage(
         &self,
@@ -126,7 +194,7 @@ impl<T: Runtime> Rpc<T> {
         hash: Option<T::Hash>,
     ) -> Result<Option<StorageData>, Error> {
         let params = Params::Array(vec![to_json_value(key)?, to_json_value(hash)?]);
-        let data = self.client.request("state_getStorage", params).await?;
+        let data = self.client()?.request("state_getStorage", params).await?;
         log::debug!("state_getStorage {:?}", data);
         Ok(data)
     }
@@ -147,7 +215,7 @@ impl<T: Runtime> Rpc<T> {
             to_json_value(start_key)?,
             to_json_value(hash)?,
         ]);
-        let data = self.client.request("state_getKeysPaged", params).await?;
+        let data = self.client()?.request("state_getKeysPaged", params).await?;
         log::debug!("state_getKeysPaged {:?}", data);
         Ok(data)
     }
@@ -164,7 +232,7 @@ impl<T: Runtime> Rpc<T> {
             to_json_value(from)?,
             to_json_value(to)?,
         ]);
-        self.client
+        self.client()?
             .request("state_queryStorage", params)
             .await
             .map_err(Into::into)
@@ -177,7 +245,7 @@ impl<T: Runtime> Rpc<T> {
         at: Option<T::Hash>,
     ) -> Result<Vec<StorageChangeSet<<T as System>::Hash>>, Error> {
         let params = Params::Array(vec![to_json_value(keys)?, to_json_value(at)?]);
-        self.client
+        self.client()?
             .request("state_queryStorage", params)
             .await
             .map_err(Into::into)
@@ -188,7 +256,7 @@ impl<T: Runtime> Rpc<T> {
         let block_zero = Some(ListOrValue::Value(NumberOrHex::Number(0)));
         let params = Params::Array(vec![to_json_value(block_zero)?]);
         let list_or_value: ListOrValue<Option<T::Hash>> =
-            self.client.request("chain_getBlockHash", params).await?;
+            self.client()?.request("chain_getBlockHash", params).await?;
         match list_or_value {
             ListOrValue::Value(genesis_hash) => {
                 genesis_hash.ok_or_else(|| "Genesis hash not found".into())
@@ -199,13 +267,24 @@ impl<T: Runtime> Rpc<T> {
 
     /// Fetch the metadata
     pub async fn metadata(&self) -> Result<Metadata, Error> {
+        let bytes = self.metadata_bytes().await?;
+        Ok(Metadata::from_bytes(&bytes)?)
+    }
+
+    /// Fetch the metadata of the runtime at the given block.
+    pub async fn metadata_at(&self, hash: T::Hash) -> Result<Metadata, Error> {
+        let params = Params::Array(vec![to_json_value(hash)?]);
+        let bytes: Bytes = self.client()?.request("state_getMetadata", params).await?;
+        Ok(Metadata::from_bytes(&bytes)?)
+    }
+
+    /// Fetch the encoded metadata, as accepted by `Metadata::from_bytes`.
+    pub async fn metadata_bytes(&self) -> Result<Vec<u8>, Error> {
         let bytes: Bytes = self
-            .client
+            .client()?
             .request("state_getMetadata", Params::None)
             .await?;
-        let meta: RuntimeMetadataPrefixed = Decode::decode(&mut &bytes[..])?;
-        let metadata: Metadata = meta.try_into()?;
-        Ok(metadata)
+        Ok(bytes.0)
     }
 
     /// Get a header
@@ -214,7 +293,7 @@ impl<T: Runtime> Rpc<T> {
         hash: Option<T::Hash>,
     ) -> Result<Option<T::Header>, Error> {
         let params = Params::Array(vec![to_json_value(hash)?]);
-        let header = self.client.request("chain_getHeader", params).await?;
+        let header = self.client()?.request("chain_getHeader", params).await?;
         Ok(header)
     }
 
@@ -225,7 +304,7 @@ impl<T: Runtime> Rpc<T> {
     ) -> Result<Option<T::Hash>, Error> {
         let block_number = block_number.map(ListOrValue::Value);
         let params = Params::Array(vec![to_json_value(block_number)?]);
-        let list_or_value = self.client.request("chain_getBlockHash", params).await?;
+        let list_or_value = self.client()?.request("chain_getBlockHash", params).await?;
         match list_or_value {
             ListOrValue::Value(hash) => Ok(hash),
             ListOrValue::List(_) => Err("Expected a Value, got a List".into()),
@@ -235,7 +314,7 @@ impl<T: Runtime> Rpc<T> {
     /// Get a block hash of the latest finalized block
     pub async fn finalized_head(&self) -> Result<T::Hash, Error> {
         let hash = self
-            .client
+            .client()?
             .request("chain_getFinalizedHead", Params::None)
             .await?;
         Ok(hash)
@@ -247,7 +326,7 @@ impl<T: Runtime> Rpc<T> {
         hash: Option<T::Hash>,
     ) -> Result<Option<ChainBlock<T>>, Error> {
         let params = Params::Array(vec![to_json_value(hash)?]);
-        let block = self.client.request("chain_getBlock", params).await?;
+        let block = self.client()?.request("chain_getBlock", params).await?;
         Ok(block)
     }
 
@@ -258,7 +337,7 @@ impl<T: Runtime> Rpc<T> {
         hash: Option<T::Hash>,
     ) -> Result<ReadProof<T::Hash>, Error> {
         let params = Params::Array(vec![to_json_value(keys)?, to_json_value(hash)?]);
-        let proof = self.client.request("state_getReadProof", params).await?;
+        let proof = self.client()?.request("state_getReadProof", params).await?;
         Ok(proof)
     }
 
@@ -269,7 +348,7 @@ impl<T: Runtime> Rpc<T> {
     ) -> Result<RuntimeVersion, Error> {
         let params = Params::Array(vec![to_json_value(at)?]);
         let version = self
-            .client
+            .client()?
             .request("state_getRuntimeVersion", params)
             .await?;
         Ok(version)
@@ -279,15 +358,14 @@ impl<T: Runtime> Rpc<T> {
     pub async fn subscribe_events(
         &self,
     ) -> Result<Subscription<StorageChangeSet<T::Hash>>, Error> {
-        let mut storage_key = twox_128(b"System").to_vec();
-        storage_key.extend(twox_128(b"Events").to_vec());
-        log::debug!("Events storage key {:?}", hex::encode(&storage_key));
+        let storage_key = events_storage_key();
+        log::debug!("Events storage key {:?}", hex::encode(&storage_key.0));
 
-        let keys = Some(vec![StorageKey(storage_key)]);
+        let keys = Some(vec![storage_key]);
         let params = Params::Array(vec![to_json_value(keys)?]);
 
         let subscription = self
-            .client
+            .client()?
             .subscribe("state_subscribeStorage", params, "state_unsubscribeStorage")
             .await?;
         Ok(subscription)
@@ -296,7 +374,7 @@ impl<T: Runtime> Rpc<T> {
     /// Subscribe to blocks.
     pub async fn subscribe_blocks(&self) -> Result<Subscription<T::Header>, Error> {
         let subscription = self
-            .client
+            .client()?
             .subscribe(
                 "chain_subscribeNewHeads",
                 Params::None,
@@ -312,7 +390,7 @@ impl<T: Runtime> Rpc<T> {
         &self,
     ) -> Result<Subscription<T::Header>, Error> {
         let subscription = self
-            .client
+            .client()?
             .subscribe(
                 "chain_subscribeFinalizedHeads",
                 Params::None,
@@ -322,6 +400,21 @@ impl<T: Runtime> Rpc<T> {
         Ok(subscription)
     }
 
+    /// Subscribe to runtime version changes.
+    pub async fn subscribe_runtime_version(
+        &self,
+    ) -> Result<Subscription<RuntimeVersion>, Error> {
+        let subscription = self
+            .client()?
+            .subscribe(
+                "state_subscribeRuntimeVersion",
+                Params::None,
+                "state_unsubscribeRuntimeVersion",
+            )
+            .await?;
+        Ok(subscription)
+    }
+
     /// Create and submit an extrinsic and return corresponding Hash if successful
     pub async fn submit_extrinsic<E: Encode>(
         &self,
@@ -330,7 +423,7 @@ impl<T: Runtime> Rpc<T> {
         let bytes: Bytes = extrinsic.encode().into();
         let params = Params::Array(vec![to_json_value(bytes)?]);
         let xt_hash = self
-            .client
+            .client()?
             .request("author_submitExtrinsic", params)
             .await?;
         Ok(xt_hash)
@@ -343,7 +436,7 @@ impl<T: Runtime> Rpc<T> {
         let bytes: Bytes = extrinsic.encode().into();
         let params = Params::Array(vec![to_json_value(bytes)?]);
         let subscription = self
-            .client
+            .client()?
             .subscribe(
                 "author_submitAndWatchExtrinsic",
                 params,
@@ -353,82 +446,153 @@ impl<T: Runtime> Rpc<T> {
         Ok(subscription)
     }
 
-    /// Create and submit an extrinsic and return corresponding Event if successful
-    pub async fn submit_and_watch_extrinsic<E: Encode + 'static>(
+    /// Fetch and decode the events of the given block, or the best block if `None`.
+    pub async fn events(
         &self,
-        extrinsic: E,
-        decoder: EventsDecoder<T>,
+        hash: Option<T::Hash>,
+        decoder: &EventsDecoder<T>,
+    ) -> Result<Vec<EventRecord<T>>, Error> {
+        match self.storage(&events_storage_key(), hash).await? {
+            Some(data) => decoder.decode_event_records(&mut &data.0[..]),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Fetch the events emitted by an extrinsic included in the given block.
+    ///
+    /// Returns `Error::Runtime` if the extrinsic failed.
+    pub async fn extrinsic_events(
+        &self,
+        block_hash: T::Hash,
+        ext_hash: T::Hash,
+        decoder: &EventsDecoder<T>,
     ) -> Result<ExtrinsicSuccess<T>, Error> {
-        let ext_hash = T::Hashing::hash_of(&extrinsic);
-        log::info!("Submitting Extrinsic `{:?}`", ext_hash);
-
-        let events_sub = self.subscribe_events().await?;
-        let mut xt_sub = self.watch_extrinsic(extrinsic).await?;
-
-        while let status = xt_sub.next().await {
-            log::info!("received status {:?}", status);
-            match status {
-                // ignore in progress extrinsic for now
-                TransactionStatus::Future
-                | TransactionStatus::Ready
-                | TransactionStatus::Broadcast(_) => continue,
-                TransactionStatus::InBlock(block_hash) => {
-                    log::info!("Fetching block {:?}", block_hash);
-                    let block = self.block(Some(block_hash)).await?;
-                    return match block {
-                        Some(signed_block) => {
-                            log::info!(
-                                "Found block {:?}, with {} extrinsics",
-                                block_hash,
-                                signed_block.block.extrinsics.len()
-                            );
-                            let ext_index = signed_block
-                                .block
-                                .extrinsics
-                                .iter()
-                                .position(|ext| {
-                                    let hash = T::Hashing::hash_of(ext);
-                                    hash == ext_hash
-                                })
-                                .ok_or_else(|| {
-                                    Error::Other(format!(
-                                        "Failed to find Extrinsic with hash {:?}",
-                                        ext_hash,
-                                    ))
-                                })?;
-                            let mut sub = EventSubscription::new(events_sub, decoder);
-                            sub.filter_extrinsic(block_hash, ext_index);
-                            let mut events = vec![];
-                            while let Some(event) = sub.next().await {
-                                events.push(event?);
-                            }
-                            Ok(ExtrinsicSuccess {
-                                block: block_hash,
-                                extrinsic: ext_hash,
-                                events,
-                            })
-                        }
-                        None => {
-                            Err(format!("Failed to find block {:?}", block_hash).into())
-                        }
-                    }
-                }
-                TransactionStatus::Invalid => return Err("Extrinsic Invalid".into()),
-                TransactionStatus::Usurped(_) => return Err("Extrinsic Usurped".into()),
-                TransactionStatus::Dropped => return Err("Extrinsic Dropped".into()),
-                TransactionStatus::Retracted(_) => {
-                    return Err("Extrinsic Retracted".into())
-                }
-                // should have made it `InBlock` before either of these
-                TransactionStatus::Finalized(_) => {
-                    return Err("Extrinsic Finalized".into())
-                }
-                TransactionStatus::FinalityTimeout(_) => {
-                    return Err("Extrinsic FinalityTimeout".into())
-                }
+        let block = self.block(Some(block_hash)).await?.ok_or_else(|| {
+            Error::Other(format!("Failed to find block {:?}", block_hash))
+        })?;
+        let ext_index = block
+            .block
+            .extrinsics
+            .iter()
+            .position(|ext| T::Hashing::hash_of(ext) == ext_hash)
+            .ok_or_else(|| {
+                Error::Other(
+                    format!("Failed to find Extrinsic with hash {:?}", ext_hash,),
+                )
+            })?;
+        let mut events = vec![];
+        for record in self.events(Some(block_hash), decoder).await? {
+            if record.phase != Phase::ApplyExtrinsic(ext_index as u32) {
+                continue
+            }
+            match record.raw {
+                Raw::Event(event) => events.push(event),
+                Raw::Error(err) => return Err(err.into()),
             }
         }
-        unreachable!()
+        Ok(ExtrinsicSuccess {
+            block: block_hash,
+            extrinsic: ext_hash,
+            events,
+        })
+    }
+
+    /// Fetch the next index of an account, taking pending transactions in the
+    /// transaction pool into account.
+    pub async fn system_account_next_index(
+        &self,
+        account: &T::AccountId,
+    ) -> Result<T::Index, Error> {
+        let params = Params::Array(vec![to_json_value(account)?]);
+        Ok(self
+            .client()?
+            .request("system_accountNextIndex", params)
+            .await?)
+    }
+
+    /// Fetch the health of the node.
+    pub async fn system_health(&self) -> Result<Health, Error> {
+        Ok(self
+            .client()?
+            .request("system_health", Params::None)
+            .await?)
+    }
+
+    /// Fetch the peers the node is connected to.
+    pub async fn system_peers(
+        &self,
+    ) -> Result<Vec<PeerInfo<T::Hash, T::BlockNumber>>, Error> {
+        Ok(self.client()?.request("system_peers", Params::None).await?)
+    }
+
+    /// Fetch the name of the chain.
+    pub async fn system_chain(&self) -> Result<String, Error> {
+        Ok(self.client()?.request("system_chain", Params::None).await?)
+    }
+
+    /// Fetch the name of the node implementation.
+    pub async fn system_name(&self) -> Result<String, Error> {
+        Ok(self.client()?.request("system_name", Params::None).await?)
+    }
+
+    /// Fetch the version of the node implementation.
+    pub async fn system_version(&self) -> Result<String, Error> {
+        Ok(self
+            .client()?
+            .request("system_version", Params::None)
+            .await?)
+    }
+
+    /// Fetch the properties of the chain.
+    pub async fn system_properties(&self) -> Result<SystemProperties, Error> {
+        Ok(self
+            .client()?
+            .request("system_properties", Params::None)
+            .await?)
+    }
+
+    /// Fetch the block synchronization state of the node.
+    pub async fn system_sync_state(&self) -> Result<SyncState<T::BlockNumber>, Error> {
+        Ok(self
+            .client()?
+            .request("system_syncState", Params::None)
+            .await?)
+    }
+
+    /// Fetch the state of the network.
+    ///
+    /// This is an unsafe RPC method.
+    pub async fn system_network_state(&self) -> Result<serde_json::Value, Error> {
+        Ok(self
+            .client()?
+            .request("system_networkState", Params::None)
+            .await?)
+    }
+
+    /// Fetch the dispatch info of an extrinsic, including its expected fee.
+    pub async fn query_info<E: Encode>(
+        &self,
+        extrinsic: E,
+        at: Option<T::Hash>,
+    ) -> Result<RuntimeDispatchInfo<T::Balance>, Error>
+    where
+        T: Balances,
+    {
+        let bytes: Bytes = extrinsic.encode().into();
+        let params = Params::Array(vec![to_json_value(bytes)?, to_json_value(at)?]);
+        Ok(self.client()?.request("payment_queryInfo", params).await?)
+    }
+
+    /// Dry run an extrinsic at the given block, or the best block if `None`.
+    pub async fn dry_run<E: Encode>(
+        &self,
+        extrinsic: E,
+        at: Option<T::Hash>,
+    ) -> Result<ApplyExtrinsicResult, Error> {
+        let bytes: Bytes = extrinsic.encode().into();
+        let params = Params::Array(vec![to_json_value(bytes)?, to_json_value(at)?]);
+        let result: Bytes = self.client()?.request("system_dryRun", params).await?;
+        Ok(Decode::decode(&mut &result[..])?)
     }
 
     /// Insert a key into the keystore.
@@ -443,14 +607,14 @@ impl<T: Runtime> Rpc<T> {
             to_json_value(suri)?,
             to_json_value(public)?,
         ]);
-        self.client.request("author_insertKey", params).await?;
+        self.client()?.request("author_insertKey", params).await?;
         Ok(())
     }
 
     /// Generate new session keys and returns the corresponding public keys.
     pub async fn rotate_keys(&self) -> Result<Bytes, Error> {
         Ok(self
-            .client
+            .client()?
             .request("author_rotateKeys", Params::None)
             .await?)
     }
@@ -462,7 +626,10 @@ impl<T: Runtime> Rpc<T> {
     /// Returns `true` iff all private keys could be found.
     pub async fn has_session_keys(&self, session_keys: Bytes) -> Result<bool, Error> {
         let params = Params::Array(vec![to_json_value(session_keys)?]);
-        Ok(self.client.request("author_hasSessionKeys", params).await?)
+        Ok(self
+            .client()?
+            .request("author_hasSessionKeys", params)
+            .await?)
     }
 
     /// Checks if the keystore has private keys for the given public key and key type.
@@ -475,10 +642,17 @@ impl<T: Runtime> Rpc<T> {
     ) -> Result<bool, Error> {
         let params =
             Params::Array(vec![to_json_value(public_key)?, to_json_value(key_type)?]);
-        Ok(self.client.request("author_hasKey", params).await?)
+        Ok(self.client()?.request("author_hasKey", params).await?)
     }
 }
 
+/// Storage key of the events of the `System` module.
+fn events_storage_key() -> StorageKey {
+    let mut storage_key = twox_128(b"System").to_vec();
+    storage_key.extend(twox_128(b"Events").to_vec());
+    StorageKey(storage_key)
+}
+
 /// Captures data for when an extrinsic is successfully included in a block
 #[derive(Debug)]
 pub struct ExtrinsicSuccess<T: System> {
@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with substrate-subxt.  If not, see <http://www.gnu.org/licenses/>.
 
-use codec::Encode;
+use codec::{
+    Decode,
+    Encode,
+};
 use sp_runtime::{
     generic::Header,
     traits::{
@@ -39,15 +42,21 @@ use crate::{
         contracts::Contracts,
         sudo::Sudo,
         system::System,
+        transaction_payment::TransactionPayment,
     },
+    types::TypeRegistry,
 };
 
 /// Runtime trait.
 pub trait Runtime: System + Sized + Send + Sync + 'static {
     /// Signature type.
-    type Signature: Verify + Encode + Send + Sync + 'static;
+    type Signature: Verify + Encode + Decode + Send + Sync + 'static;
     /// Transaction extras.
     type Extra: SignedExtra<Self> + Send + Sync + 'static;
+
+    /// Registers the types of the runtime's modules, in addition to the system types,
+    /// so values of them can be decoded dynamically.
+    fn register_types(_types: &mut TypeRegistry) {}
 }
 
 /// Concrete type definitions compatible with those in the default substrate `node_runtime`
@@ -62,6 +71,10 @@ pub struct DefaultNodeRuntime;
 impl Runtime for DefaultNodeRuntime {
     type Signature = MultiSignature;
     type Extra = DefaultExtra<Self>;
+
+    fn register_types(types: &mut TypeRegistry) {
+        types.with_balances::<Self>();
+    }
 }
 
 impl System for DefaultNodeRuntime {
@@ -82,6 +95,8 @@ impl Balances for DefaultNodeRuntime {
 
 impl Contracts for DefaultNodeRuntime {}
 
+impl TransactionPayment for DefaultNodeRuntime {}
+
 impl Sudo for DefaultNodeRuntime {}
 
 /// Concrete type definitions compatible with the node template.
@@ -96,6 +111,10 @@ pub struct NodeTemplateRuntime;
 impl Runtime for NodeTemplateRuntime {
     type Signature = MultiSignature;
     type Extra = DefaultExtra<Self>;
+
+    fn register_types(types: &mut TypeRegistry) {
+        types.with_balances::<Self>();
+    }
 }
 
 impl System for NodeTemplateRuntime {
@@ -116,6 +135,8 @@ impl Balances for NodeTemplateRuntime {
 
 impl Sudo for NodeTemplateRuntime {}
 
+impl TransactionPayment for NodeTemplateRuntime {}
+
 /// Concrete type definitions compatible with those for kusama, v0.7
 ///
 /// # Note
@@ -128,6 +149,10 @@ pub struct KusamaRuntime;
 impl Runtime for KusamaRuntime {
     type Signature = MultiSignature;
     type Extra = DefaultExtra<Self>;
+
+    fn register_types(types: &mut TypeRegistry) {
+        types.with_balances::<Self>();
+    }
 }
 
 impl System for KusamaRuntime {
@@ -145,3 +170,5 @@ impl System for KusamaRuntime {
 impl Balances for KusamaRuntime {
     type Balance = u128;
 }
+
+impl TransactionPayment for KusamaRuntime {}
@@ -14,22 +14,45 @@
 // You should have received a copy of the GNU General Public License
 // along with substrate-subxt.  If not, see <http://www.gnu.org/licenses/>.
 
+use futures::{
+    channel::mpsc,
+    future,
+    Future,
+    StreamExt,
+};
 use jsonrpsee::client::Subscription;
 use sp_core::storage::StorageChangeSet;
-use std::collections::VecDeque;
+use sp_runtime::traits::{
+    Header,
+    One,
+};
+use sp_transaction_pool::TransactionStatus;
+use sp_version::RuntimeVersion;
+use std::{
+    collections::VecDeque,
+    time::Duration,
+};
 
 use crate::{
     error::Error,
     events::{
+        EventRecord,
         EventsDecoder,
         Raw,
         RawEvent,
     },
+    extrinsic::DecodedExtrinsic,
     frame::{
         system::Phase,
         Event,
     },
+    nonce::NonceManager,
+    rpc::{
+        ExtrinsicSuccess,
+        Rpc,
+    },
     runtimes::Runtime,
+    Client,
 };
 
 /// Event subscription simplifies filtering a storage change set stream for
@@ -40,6 +63,8 @@ pub struct EventSubscription<T: Runtime> {
     block: Option<T::Hash>,
     extrinsic: Option<usize>,
     event: Option<(&'static str, &'static str)>,
+    topic: Option<T::Hash>,
+    all_phases: bool,
     events: VecDeque<RawEvent>,
     finished: bool,
 }
@@ -56,6 +81,8 @@ impl<T: Runtime> EventSubscription<T> {
             block: None,
             extrinsic: None,
             event: None,
+            topic: None,
+            all_phases: false,
             events: Default::default(),
             finished: false,
         }
@@ -77,6 +104,19 @@ impl<T: Runtime> EventSubscription<T> {
         self.event = Some((E::MODULE, E::EVENT));
     }
 
+    /// Only returns events deposited with the given topic.
+    pub fn filter_topic(&mut self, topic: T::Hash) {
+        self.topic = Some(topic);
+    }
+
+    /// Also returns events emitted while initializing or finalizing a block, in
+    /// addition to the ones emitted by extrinsics.
+    ///
+    /// Has no effect when filtering by extrinsic.
+    pub fn include_all_phases(&mut self) {
+        self.all_phases = true;
+    }
+
     /// Gets the next event.
     pub async fn next(&mut self) -> Option<Result<RawEvent, Error>> {
         loop {
@@ -96,31 +136,424 @@ impl<T: Runtime> EventSubscription<T> {
             }
             for (_key, data) in change_set.changes {
                 if let Some(data) = data {
-                    let raw_events = match self.decoder.decode_events(&mut &data.0[..]) {
-                        Ok(events) => events,
-                        Err(error) => return Some(Err(error)),
-                    };
-                    for (phase, raw) in raw_events {
-                        if let Phase::ApplyExtrinsic(i) = phase {
-                            if let Some(ext_index) = self.extrinsic {
-                                if i as usize != ext_index {
-                                    continue
-                                }
-                            }
-                            let event = match raw {
-                                Raw::Event(event) => event,
-                                Raw::Error(err) => return Some(Err(err.into())),
-                            };
-                            if let Some((module, variant)) = self.event {
-                                if event.module != module || event.variant != variant {
-                                    continue
-                                }
-                            }
-                            self.events.push_back(event);
-                        }
+                    let records =
+                        match self.decoder.decode_event_records(&mut &data.0[..]) {
+                            Ok(records) => records,
+                            Err(error) => return Some(Err(error)),
+                        };
+                    if let Err(error) = self.push_events(records) {
+                        return Some(Err(error))
                     }
                 }
             }
         }
     }
+
+    /// Queues the events of `records` which pass the filters.
+    fn push_events(&mut self, records: Vec<EventRecord<T>>) -> Result<(), Error> {
+        for record in records {
+            let ext_index = match record.phase {
+                Phase::ApplyExtrinsic(i) => Some(i as usize),
+                Phase::Finalization | Phase::Initialization if self.all_phases => None,
+                _ => continue,
+            };
+            if self.extrinsic.is_some() && self.extrinsic != ext_index {
+                continue
+            }
+            if let Some(topic) = self.topic.as_ref() {
+                if !record.topics.contains(topic) {
+                    continue
+                }
+            }
+            let event = match record.raw {
+                Raw::Event(event) => event,
+                Raw::Error(err) => return Err(err.into()),
+            };
+            if let Some((module, variant)) = self.event {
+                if event.module != module || event.variant != variant {
+                    continue
+                }
+            }
+            self.events.push_back(event);
+        }
+        Ok(())
+    }
+}
+
+/// Progress of a submitted extrinsic through the transaction pool and the chain.
+pub struct TransactionProgress<T: Runtime> {
+    rpc: Rpc<T>,
+    subscription: Subscription<TransactionStatus<T::Hash, T::Hash>>,
+    decoder: EventsDecoder<T>,
+    ext_hash: T::Hash,
+    timeout: Option<Duration>,
+    nonce: Option<(NonceManager<T>, T::AccountId)>,
+    finished: bool,
+}
+
+impl<T: Runtime> TransactionProgress<T> {
+    pub(crate) fn new(
+        rpc: Rpc<T>,
+        subscription: Subscription<TransactionStatus<T::Hash, T::Hash>>,
+        decoder: EventsDecoder<T>,
+        ext_hash: T::Hash,
+        timeout: Option<Duration>,
+    ) -> Self {
+        Self {
+            rpc,
+            subscription,
+            decoder,
+            ext_hash,
+            timeout,
+            nonce: None,
+            finished: false,
+        }
+    }
+
+    /// Resyncs the nonce of `account` if the extrinsic leaves the pool without being
+    /// included, as its nonce is then free again.
+    pub(crate) fn resync_nonce_of(
+        mut self,
+        nonces: NonceManager<T>,
+        account: T::AccountId,
+    ) -> Self {
+        self.nonce = Some((nonces, account));
+        self
+    }
+
+    /// Returns the hash of the extrinsic.
+    pub fn extrinsic_hash(&self) -> T::Hash {
+        self.ext_hash
+    }
+
+    /// Gets the next status of the extrinsic.
+    ///
+    /// Returns `None` once a status was returned after which the extrinsic won't
+    /// make any more progress.
+    pub async fn next(&mut self) -> Option<TransactionStatus<T::Hash, T::Hash>> {
+        if self.finished {
+            return None
+        }
+        let status = self.subscription.next().await;
+        log::info!("received status {:?}", status);
+        match status {
+            TransactionStatus::Future
+            | TransactionStatus::Ready
+            | TransactionStatus::Broadcast(_)
+            | TransactionStatus::InBlock(_)
+            | TransactionStatus::Retracted(_) => {}
+            TransactionStatus::Finalized(_) | TransactionStatus::FinalityTimeout(_) => {
+                self.finished = true
+            }
+            TransactionStatus::Usurped(_)
+            | TransactionStatus::Dropped
+            | TransactionStatus::Invalid => {
+                if let Some((nonces, account)) = &self.nonce {
+                    nonces.reset(account).await;
+                }
+                self.finished = true
+            }
+        }
+        Some(status)
+    }
+
+    /// Waits until the extrinsic is included in a block and returns the events it
+    /// emitted in that block.
+    ///
+    /// Fails with `Error::Timeout` if the watch timeout of the client elapses first.
+    pub async fn wait_for_in_block(mut self) -> Result<ExtrinsicSuccess<T>, Error> {
+        let timeout = self.timeout;
+        with_timeout(timeout, async move {
+            while let Some(status) = self.next().await {
+                match status {
+                    TransactionStatus::InBlock(block_hash)
+                    | TransactionStatus::Finalized(block_hash) => {
+                        return self.events(block_hash).await
+                    }
+                    status => self.check(status)?,
+                }
+            }
+            Err(self.finished_error())
+        })
+        .await
+    }
+
+    /// Waits until the block including the extrinsic is finalized and returns the
+    /// events the extrinsic emitted in that block.
+    ///
+    /// Fails with `Error::Timeout` if the watch timeout of the client elapses first.
+    pub async fn wait_for_finalized(mut self) -> Result<ExtrinsicSuccess<T>, Error> {
+        let timeout = self.timeout;
+        with_timeout(timeout, async move {
+            while let Some(status) = self.next().await {
+                match status {
+                    TransactionStatus::Finalized(block_hash) => {
+                        return self.events(block_hash).await
+                    }
+                    status => self.check(status)?,
+                }
+            }
+            Err(self.finished_error())
+        })
+        .await
+    }
+
+    async fn events(&self, block_hash: T::Hash) -> Result<ExtrinsicSuccess<T>, Error> {
+        log::info!("Fetching events of block {:?}", block_hash);
+        self.rpc
+            .extrinsic_events(block_hash, self.ext_hash, &self.decoder)
+            .await
+    }
+
+    fn check(&self, status: TransactionStatus<T::Hash, T::Hash>) -> Result<(), Error> {
+        match status {
+            TransactionStatus::Invalid => Err("Extrinsic Invalid".into()),
+            TransactionStatus::Usurped(_) => Err("Extrinsic Usurped".into()),
+            TransactionStatus::Dropped => Err("Extrinsic Dropped".into()),
+            TransactionStatus::FinalityTimeout(_) => {
+                Err("Extrinsic FinalityTimeout".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn finished_error(&self) -> Error {
+        format!("Extrinsic {:?} made no further progress", self.ext_hash).into()
+    }
+}
+
+/// Runs `watch` to completion, unless `timeout` elapses first.
+async fn with_timeout<F, R>(timeout: Option<Duration>, watch: F) -> Result<R, Error>
+where
+    F: Future<Output = Result<R, Error>>,
+{
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return watch.await,
+    };
+    futures::pin_mut!(watch);
+    match future::select(watch, futures_timer::Delay::new(timeout)).await {
+        future::Either::Left((result, _)) => result,
+        future::Either::Right(_) => Err(Error::Timeout(timeout)),
+    }
+}
+
+/// Stream of the runtime upgrades a client followed, each notified once the client
+/// switched to the metadata and runtime version of the new runtime.
+pub struct RuntimeUpgrades {
+    receiver: mpsc::UnboundedReceiver<RuntimeVersion>,
+}
+
+impl RuntimeUpgrades {
+    pub(crate) fn new(receiver: mpsc::UnboundedReceiver<RuntimeVersion>) -> Self {
+        Self { receiver }
+    }
+
+    /// Waits for the next runtime upgrade and returns the new runtime version.
+    ///
+    /// Returns `None` once the client stopped following the runtime, after all its
+    /// clones were dropped.
+    pub async fn next(&mut self) -> Option<RuntimeVersion> {
+        self.receiver.next().await
+    }
+}
+
+/// Finalized block with its decoded extrinsics and the events they emitted.
+pub struct BlockWithEvents<T: Runtime> {
+    /// Hash of the block.
+    pub hash: T::Hash,
+    /// Header of the block.
+    pub header: T::Header,
+    /// Extrinsics of the block, in order, each with the events it emitted.
+    pub extrinsics: Vec<ExtrinsicWithEvents<T>>,
+    /// Events emitted while initializing or finalizing the block.
+    pub block_events: Vec<EventRecord<T>>,
+}
+
+/// Extrinsic of a block with the events it emitted.
+pub struct ExtrinsicWithEvents<T: Runtime> {
+    /// The decoded extrinsic.
+    pub extrinsic: DecodedExtrinsic<T>,
+    /// Events emitted by the extrinsic.
+    pub events: Vec<EventRecord<T>>,
+}
+
+/// Stream of finalized blocks with their extrinsics and events.
+pub struct FinalizedBlocks<T: Runtime> {
+    client: Client<T>,
+    subscription: Subscription<T::Header>,
+    last: Option<T::BlockNumber>,
+    headers: VecDeque<T::Header>,
+}
+
+impl<T: Runtime> FinalizedBlocks<T> {
+    pub(crate) fn new(client: Client<T>, subscription: Subscription<T::Header>) -> Self {
+        Self {
+            client,
+            subscription,
+            last: None,
+            headers: Default::default(),
+        }
+    }
+
+    /// Waits for the next finalized block.
+    ///
+    /// Blocks are returned in order without gaps, including the ones skipped by the
+    /// finalized head subscription when several blocks are finalized at once.
+    pub async fn next(&mut self) -> Result<BlockWithEvents<T>, Error> {
+        while self.headers.is_empty() {
+            let header = self.subscription.next().await;
+            self.headers = self.headers_since_last(header).await?;
+        }
+        let header = self.headers.pop_front().expect("checked above; qed");
+        match self.block(header.clone()).await {
+            Ok(block) => {
+                self.last = Some(*block.header.number());
+                Ok(block)
+            }
+            Err(err) => {
+                // retry the same block on the next call, so none is skipped
+                self.headers.push_front(header);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the headers of the blocks after the last returned one, up to and
+    /// including `header`, by following the parents of `header`.
+    async fn headers_since_last(
+        &self,
+        header: T::Header,
+    ) -> Result<VecDeque<T::Header>, Error> {
+        let mut headers = VecDeque::new();
+        let last = match self.last {
+            Some(last) => last,
+            None => {
+                headers.push_back(header);
+                return Ok(headers)
+            }
+        };
+        let mut header = header;
+        while *header.number() > last {
+            let parent = *header.parent_hash();
+            let number = *header.number();
+            headers.push_front(header);
+            if number == last + One::one() {
+                break
+            }
+            log::debug!("Fetching skipped finalized block {:?}", parent);
+            header = self
+                .client
+                .header(Some(parent))
+                .await?
+                .ok_or_else(|| format!("Header of block {:?} not found", parent))?;
+        }
+        Ok(headers)
+    }
+
+    async fn block(&self, header: T::Header) -> Result<BlockWithEvents<T>, Error> {
+        let hash = header.hash();
+        let metadata = self.client.metadata_at(hash).await?;
+        let mut extrinsics = self
+            .client
+            .block_extrinsics_with_metadata(hash, &metadata)
+            .await?
+            .ok_or_else(|| format!("Block {:?} not found", hash))?
+            .into_iter()
+            .map(|extrinsic| {
+                ExtrinsicWithEvents {
+                    extrinsic,
+                    events: Vec::new(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut block_events = Vec::new();
+        for record in self.client.events_with_metadata(hash, metadata).await? {
+            match record.phase {
+                Phase::ApplyExtrinsic(index) => {
+                    extrinsics
+                        .get_mut(index as usize)
+                        .ok_or_else(|| {
+                            format!(
+                                "Event of extrinsic {} not in block {:?}",
+                                index, hash
+                            )
+                        })?
+                        .events
+                        .push(record)
+                }
+                Phase::Finalization | Phase::Initialization => block_events.push(record),
+            }
+        }
+        Ok(BlockWithEvents {
+            hash,
+            header,
+            extrinsics,
+            block_events,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        frame::balances,
+        tests::{
+            test_client,
+            TestRuntime,
+        },
+    };
+    use codec::{
+        Compact,
+        Encode,
+    };
+    use sp_core::H256;
+    use sp_keyring::AccountKeyring;
+
+    #[async_std::test]
+    async fn test_filter_topic() {
+        let (client, _) = test_client().await;
+        let metadata = client.metadata();
+        let module = metadata
+            .modules_with_events()
+            .find(|module| module.name() == "Balances")
+            .unwrap();
+        let transfer = module
+            .events()
+            .position(|event| event.name == "Transfer")
+            .unwrap() as u8;
+        let record = |topics: Vec<H256>| {
+            // `Phase::ApplyExtrinsic(0)`
+            let mut record = vec![0];
+            0u32.encode_to(&mut record);
+            record.push(module.index());
+            record.push(transfer);
+            AccountKeyring::Alice.to_account_id().encode_to(&mut record);
+            AccountKeyring::Bob.to_account_id().encode_to(&mut record);
+            10_000u128.encode_to(&mut record);
+            topics.encode_to(&mut record);
+            record
+        };
+        let topic = H256::repeat_byte(1);
+        let other = H256::repeat_byte(2);
+        let mut events = Compact(3u32).encode();
+        events.extend(record(vec![topic]));
+        events.extend(record(vec![other]));
+        events.extend(record(vec![other, topic]));
+
+        let decoder = client.events_decoder::<balances::TransferCall<TestRuntime>>();
+        let records = decoder.decode_event_records(&mut &events[..]).unwrap();
+        assert_eq!(records[2].topics, vec![other, topic]);
+
+        let mut subscription =
+            EventSubscription::new(client.subscribe_events().await.unwrap(), decoder);
+        subscription.filter_topic(topic);
+        subscription.push_events(records).unwrap();
+        assert_eq!(subscription.events.len(), 2);
+        assert!(subscription
+            .events
+            .iter()
+            .all(|event| event.module == "Balances" && event.variant == "Transfer"));
+    }
 }
@@ -0,0 +1,749 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+// This file is part of substrate-subxt.
+//
+// subxt is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// subxt is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with substrate-subxt.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Generic decoding of SCALE encoded values, described by the type names found in the
+//! metadata.
+
+use codec::{
+    Compact,
+    Decode,
+    Encode,
+    Input,
+};
+use std::collections::HashMap;
+
+use crate::{
+    error::Error,
+    frame::balances::Balances,
+    metadata::EventArg,
+    runtimes::Runtime,
+};
+
+/// Definition of a named type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TypeDef {
+    /// Value of a fixed number of bytes, whose structure is unknown.
+    Opaque(usize),
+    /// Another name of a type.
+    Alias(EventArg),
+    /// Struct with named fields.
+    Composite(Vec<(String, EventArg)>),
+    /// Enum, with its variants in the order of their indices.
+    Variant(Vec<(String, Vec<EventArg>)>),
+}
+
+/// Decoded SCALE value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    /// Boolean.
+    Bool(bool),
+    /// Unsigned integer, including compact encoded ones.
+    Uint(u128),
+    /// Signed integer.
+    Int(i128),
+    /// Bytes of an opaque type, or of a `Vec<u8>` or `[u8; N]`.
+    Bytes(Vec<u8>),
+    /// Elements of a `Vec` or an array.
+    Sequence(Vec<Value>),
+    /// Fields of a tuple.
+    Tuple(Vec<Value>),
+    /// Optional value.
+    Option(Option<Box<Value>>),
+    /// Result value.
+    Result(Result<Box<Value>, Box<Value>>),
+    /// Named fields of a struct.
+    Composite(Vec<(String, Value)>),
+    /// Name and fields of an enum variant.
+    Variant(String, Vec<Value>),
+}
+
+/// Number of bytes or values of a sequence allocated before any of them are decoded.
+const MAX_PREALLOCATION: usize = 4 * 1024;
+
+/// Registry of the type definitions used to decode values.
+#[derive(Clone, Debug)]
+pub struct TypeRegistry {
+    types: HashMap<String, TypeDef>,
+}
+
+impl Default for TypeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn named(name: &str) -> EventArg {
+    EventArg::Primitive(name.to_string())
+}
+
+fn unit_variants(names: &[&str]) -> TypeDef {
+    TypeDef::Variant(
+        names
+            .iter()
+            .map(|name| (name.to_string(), Vec::new()))
+            .collect(),
+    )
+}
+
+impl TypeRegistry {
+    /// Creates a registry with the definitions of common substrate types.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            types: HashMap::new(),
+        };
+        let bytes = EventArg::Vec(Box::new(named("u8")));
+        registry.register("Bytes", TypeDef::Alias(bytes.clone()));
+        registry.register("Text", TypeDef::Alias(bytes.clone()));
+        registry.register("String", TypeDef::Alias(bytes));
+        registry.register("H160", TypeDef::Opaque(20));
+        registry.register("H256", TypeDef::Opaque(32));
+        registry.register("H512", TypeDef::Opaque(64));
+        registry.register("Weight", TypeDef::Alias(named("u64")));
+        registry.register(
+            "DispatchClass",
+            unit_variants(&["Normal", "Operational", "Mandatory"]),
+        );
+        registry.register("Pays", unit_variants(&["Yes", "No"]));
+        registry.register(
+            "DispatchInfo",
+            TypeDef::Composite(vec![
+                ("weight".into(), named("Weight")),
+                ("class".into(), named("DispatchClass")),
+                ("pays_fee".into(), named("Pays")),
+            ]),
+        );
+        // the messages of `Other` and `Module` are skipped when encoding
+        registry.register(
+            "DispatchError",
+            TypeDef::Variant(vec![
+                ("Other".into(), vec![]),
+                ("CannotLookup".into(), vec![]),
+                ("BadOrigin".into(), vec![]),
+                ("Module".into(), vec![named("u8"), named("u8")]),
+            ]),
+        );
+        registry.register(
+            "DispatchResult",
+            TypeDef::Alias(EventArg::Result(
+                Box::new(EventArg::Tuple(vec![])),
+                Box::new(named("DispatchError")),
+            )),
+        );
+        registry
+    }
+
+    /// Registers the definition of a type, replacing any previous one.
+    pub fn register(&mut self, name: &str, def: TypeDef) {
+        self.types.insert(name.to_string(), def);
+    }
+
+    /// Registers the types of the balances module.
+    pub fn with_balances<T: Balances>(&mut self) {
+        self.register("Balance", uint::<T::Balance>());
+    }
+
+    /// Registers all definitions of `other`, replacing any previous ones.
+    pub(crate) fn extend(&mut self, other: &TypeRegistry) {
+        for (name, def) in &other.types {
+            self.register(name, def.clone());
+        }
+    }
+
+    /// Returns the definition of a type.
+    pub fn get(&self, name: &str) -> Option<&TypeDef> {
+        self.types.get(name)
+    }
+
+    /// Returns the names of the types referenced by `arg` which can't be decoded.
+    pub fn missing(&self, arg: &EventArg) -> Vec<String> {
+        let mut missing = Vec::new();
+        self.collect_missing(arg, &mut missing);
+        missing
+    }
+
+    fn collect_missing(&self, arg: &EventArg, missing: &mut Vec<String>) {
+        match arg {
+            EventArg::Primitive(name) => {
+                if is_builtin(name) {
+                    return
+                }
+                match self.types.get(name) {
+                    Some(TypeDef::Opaque(_)) => {}
+                    Some(TypeDef::Alias(arg)) => self.collect_missing(arg, missing),
+                    Some(TypeDef::Composite(fields)) => {
+                        for (_, arg) in fields {
+                            self.collect_missing(arg, missing)
+                        }
+                    }
+                    Some(TypeDef::Variant(variants)) => {
+                        for arg in variants.iter().flat_map(|(_, args)| args) {
+                            self.collect_missing(arg, missing)
+                        }
+                    }
+                    None => missing.push(name.clone()),
+                }
+            }
+            // compact values are decoded without looking at their type
+            EventArg::Compact(_) => {}
+            EventArg::Vec(arg) | EventArg::Option(arg) | EventArg::Array(arg, _) => {
+                self.collect_missing(arg, missing)
+            }
+            EventArg::Result(ok, err) => {
+                self.collect_missing(ok, missing);
+                self.collect_missing(err, missing);
+            }
+            EventArg::Tuple(args) => {
+                for arg in args {
+                    self.collect_missing(arg, missing)
+                }
+            }
+        }
+    }
+
+    /// Decodes a value of the type described by `arg`.
+    pub fn decode<I: Input>(
+        &self,
+        arg: &EventArg,
+        input: &mut I,
+    ) -> Result<Value, Error> {
+        let value = match arg {
+            EventArg::Primitive(name) => self.decode_named(name, input)?,
+            EventArg::Compact(_) => Value::Uint(<Compact<u128>>::decode(input)?.0),
+            EventArg::Vec(arg) => {
+                let len = <Compact<u32>>::decode(input)?.0 as usize;
+                self.decode_sequence(arg, len, input)?
+            }
+            EventArg::Array(arg, len) => self.decode_sequence(arg, *len, input)?,
+            EventArg::Tuple(args) => {
+                Value::Tuple(
+                    args.iter()
+                        .map(|arg| self.decode(arg, input))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+            EventArg::Option(arg) => {
+                match input.read_byte()? {
+                    0 => Value::Option(None),
+                    1 => Value::Option(Some(Box::new(self.decode(arg, input)?))),
+                    _ => return Err(codec::Error::from("Invalid Option index").into()),
+                }
+            }
+            EventArg::Result(ok, err) => {
+                match input.read_byte()? {
+                    0 => Value::Result(Ok(Box::new(self.decode(ok, input)?))),
+                    1 => Value::Result(Err(Box::new(self.decode(err, input)?))),
+                    _ => return Err(codec::Error::from("Invalid Result index").into()),
+                }
+            }
+        };
+        Ok(value)
+    }
+
+    fn decode_sequence<I: Input>(
+        &self,
+        arg: &EventArg,
+        len: usize,
+        input: &mut I,
+    ) -> Result<Value, Error> {
+        // the length is read from the input, so the buffers grow with the data which
+        // is actually there instead of being allocated up front
+        if arg == &named("u8") {
+            let mut bytes = Vec::new();
+            while bytes.len() < len {
+                let start = bytes.len();
+                let chunk = (len - start).min(start.max(MAX_PREALLOCATION));
+                bytes.resize(start + chunk, 0);
+                input.read(&mut bytes[start..])?;
+            }
+            return Ok(Value::Bytes(bytes))
+        }
+        let capacity = match input.remaining_len()? {
+            Some(remaining) => len.min(remaining),
+            None => len.min(MAX_PREALLOCATION),
+        };
+        let mut values = Vec::with_capacity(capacity);
+        for _ in 0..len {
+            values.push(self.decode(arg, input)?);
+        }
+        Ok(Value::Sequence(values))
+    }
+
+    fn decode_named<I: Input>(&self, name: &str, input: &mut I) -> Result<Value, Error> {
+        let value = match name {
+            "bool" => Value::Bool(bool::decode(input)?),
+            "u8" => Value::Uint(u8::decode(input)?.into()),
+            "u16" => Value::Uint(u16::decode(input)?.into()),
+            "u32" => Value::Uint(u32::decode(input)?.into()),
+            "u64" => Value::Uint(u64::decode(input)?.into()),
+            "u128" => Value::Uint(u128::decode(input)?),
+            "i8" => Value::Int(i8::decode(input)?.into()),
+            "i16" => Value::Int(i16::decode(input)?.into()),
+            "i32" => Value::Int(i32::decode(input)?.into()),
+            "i64" => Value::Int(i64::decode(input)?.into()),
+            "i128" => Value::Int(i128::decode(input)?),
+            _ => {
+                match self.types.get(name) {
+                    Some(TypeDef::Opaque(size)) => {
+                        let mut bytes = vec![0; *size];
+                        input.read(&mut bytes)?;
+                        Value::Bytes(bytes)
+                    }
+                    Some(TypeDef::Alias(arg)) => self.decode(arg, input)?,
+                    Some(TypeDef::Composite(fields)) => {
+                        let mut values = Vec::with_capacity(fields.len());
+                        for (name, arg) in fields {
+                            values.push((name.clone(), self.decode(arg, input)?));
+                        }
+                        Value::Composite(values)
+                    }
+                    Some(TypeDef::Variant(variants)) => {
+                        let index = input.read_byte()? as usize;
+                        let (name, args) = variants.get(index).ok_or_else(|| {
+                            codec::Error::from("Invalid enum variant index")
+                        })?;
+                        let values = args
+                            .iter()
+                            .map(|arg| self.decode(arg, input))
+                            .collect::<Result<_, _>>()?;
+                        Value::Variant(name.clone(), values)
+                    }
+                    None => return Err(Error::TypeSizeUnavailable(name.to_string())),
+                }
+            }
+        };
+        Ok(value)
+    }
+
+    /// Skips a value of the type described by `arg`, without decoding it.
+    pub(crate) fn skip(&self, arg: &EventArg, input: &mut &[u8]) -> Result<(), Error> {
+        match arg {
+            EventArg::Primitive(name) => self.skip_named(name, input)?,
+            EventArg::Compact(_) => {
+                <Compact<u128>>::decode(input)?;
+            }
+            EventArg::Vec(arg) => {
+                let len = <Compact<u32>>::decode(input)?.0 as usize;
+                self.skip_sequence(arg, len, input)?
+            }
+            EventArg::Array(arg, len) => self.skip_sequence(arg, *len, input)?,
+            EventArg::Tuple(args) => {
+                for arg in args {
+                    self.skip(arg, input)?;
+                }
+            }
+            EventArg::Option(arg) => {
+                match input.read_byte()? {
+                    0 => {}
+                    1 => self.skip(arg, input)?,
+                    _ => return Err(codec::Error::from("Invalid Option index").into()),
+                }
+            }
+            EventArg::Result(ok, err) => {
+                match input.read_byte()? {
+                    0 => self.skip(ok, input)?,
+                    1 => self.skip(err, input)?,
+                    _ => return Err(codec::Error::from("Invalid Result index").into()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_sequence(
+        &self,
+        arg: &EventArg,
+        len: usize,
+        input: &mut &[u8],
+    ) -> Result<(), Error> {
+        if arg == &named("u8") {
+            return skip_bytes(len, input)
+        }
+        for _ in 0..len {
+            self.skip(arg, input)?;
+        }
+        Ok(())
+    }
+
+    fn skip_named(&self, name: &str, input: &mut &[u8]) -> Result<(), Error> {
+        if name == "bool" {
+            bool::decode(input)?;
+            return Ok(())
+        }
+        if is_builtin(name) {
+            return skip_bytes(builtin_size(name), input)
+        }
+        match self.types.get(name) {
+            Some(TypeDef::Opaque(size)) => skip_bytes(*size, input)?,
+            Some(TypeDef::Alias(arg)) => self.skip(arg, input)?,
+            Some(TypeDef::Composite(fields)) => {
+                for (_, arg) in fields {
+                    self.skip(arg, input)?;
+                }
+            }
+            Some(TypeDef::Variant(variants)) => {
+                let index = input.read_byte()? as usize;
+                let (_, args) = variants
+                    .get(index)
+                    .ok_or_else(|| codec::Error::from("Invalid enum variant index"))?;
+                for arg in args {
+                    self.skip(arg, input)?;
+                }
+            }
+            None => return Err(Error::TypeSizeUnavailable(name.to_string())),
+        }
+        Ok(())
+    }
+}
+
+fn skip_bytes(len: usize, input: &mut &[u8]) -> Result<(), Error> {
+    if input.len() < len {
+        return Err(codec::Error::from("Not enough data to fill buffer").into())
+    }
+    *input = &input[len..];
+    Ok(())
+}
+
+impl TypeRegistry {
+    /// Encodes a value as the type described by `arg`.
+    ///
+    /// Values of types which aren't known to the registry can be given as their
+    /// encoded bytes.
+    pub fn encode(
+        &self,
+        arg: &EventArg,
+        value: &Value,
+        output: &mut Vec<u8>,
+    ) -> Result<(), Error> {
+        match (arg, value) {
+            (EventArg::Primitive(name), value) => {
+                self.encode_named(name, value, output)?
+            }
+            (EventArg::Compact(_), Value::Uint(n)) => Compact(*n).encode_to(output),
+            (EventArg::Vec(arg), Value::Bytes(bytes)) if **arg == named("u8") => {
+                bytes.encode_to(output)
+            }
+            (EventArg::Vec(arg), Value::Sequence(values)) => {
+                Compact(values.len() as u32).encode_to(output);
+                for value in values {
+                    self.encode(arg, value, output)?;
+                }
+            }
+            (EventArg::Array(arg, len), Value::Bytes(bytes))
+                if **arg == named("u8") && bytes.len() == *len =>
+            {
+                output.extend_from_slice(bytes)
+            }
+            (EventArg::Array(arg, len), Value::Sequence(values))
+                if values.len() == *len =>
+            {
+                for value in values {
+                    self.encode(arg, value, output)?;
+                }
+            }
+            (EventArg::Tuple(args), Value::Tuple(values))
+                if args.len() == values.len() =>
+            {
+                for (arg, value) in args.iter().zip(values) {
+                    self.encode(arg, value, output)?;
+                }
+            }
+            (EventArg::Option(_), Value::Option(None)) => output.push(0),
+            (EventArg::Option(arg), Value::Option(Some(value))) => {
+                output.push(1);
+                self.encode(arg, value, output)?;
+            }
+            (EventArg::Result(ok, _), Value::Result(Ok(value))) => {
+                output.push(0);
+                self.encode(ok, value, output)?;
+            }
+            (EventArg::Result(_, err), Value::Result(Err(value))) => {
+                output.push(1);
+                self.encode(err, value, output)?;
+            }
+            (arg, value) => return Err(mismatch(arg, value)),
+        }
+        Ok(())
+    }
+
+    fn encode_named(
+        &self,
+        name: &str,
+        value: &Value,
+        output: &mut Vec<u8>,
+    ) -> Result<(), Error> {
+        let arg = || named(name);
+        match (name, value) {
+            ("bool", Value::Bool(b)) => b.encode_to(output),
+            (_, Value::Uint(n)) if is_builtin(name) && name.starts_with('u') => {
+                let size = builtin_size(name);
+                if size < 16 && *n >> (size * 8) != 0 {
+                    return Err(mismatch(&arg(), value))
+                }
+                output.extend_from_slice(&n.to_le_bytes()[..size]);
+            }
+            (_, Value::Int(n)) if is_builtin(name) && name.starts_with('i') => {
+                let size = builtin_size(name);
+                let bits = size as u32 * 8;
+                if size < 16 && (*n < -(1 << (bits - 1)) || *n >= 1 << (bits - 1)) {
+                    return Err(mismatch(&arg(), value))
+                }
+                output.extend_from_slice(&n.to_le_bytes()[..size]);
+            }
+            _ => {
+                match (self.types.get(name), value) {
+                    (Some(TypeDef::Opaque(size)), Value::Bytes(bytes))
+                        if bytes.len() == *size =>
+                    {
+                        output.extend_from_slice(bytes)
+                    }
+                    // opaque integers, like a `Balance` registered by its size
+                    (Some(TypeDef::Opaque(size)), Value::Uint(n))
+                        if *size <= 16 && (*size == 16 || *n >> (size * 8) == 0) =>
+                    {
+                        output.extend_from_slice(&n.to_le_bytes()[..*size])
+                    }
+                    (Some(TypeDef::Alias(arg)), value) => {
+                        self.encode(arg, value, output)?
+                    }
+                    (Some(TypeDef::Composite(fields)), Value::Composite(values))
+                        if fields.len() == values.len()
+                            && fields
+                                .iter()
+                                .zip(values)
+                                .all(|((a, _), (b, _))| a == b) =>
+                    {
+                        for ((_, arg), (_, value)) in fields.iter().zip(values) {
+                            self.encode(arg, value, output)?;
+                        }
+                    }
+                    (Some(TypeDef::Composite(fields)), Value::Tuple(values))
+                        if fields.len() == values.len() =>
+                    {
+                        for ((_, arg), value) in fields.iter().zip(values) {
+                            self.encode(arg, value, output)?;
+                        }
+                    }
+                    (
+                        Some(TypeDef::Variant(variants)),
+                        Value::Variant(variant, values),
+                    ) => {
+                        let index = variants
+                            .iter()
+                            .position(|(name, args)| {
+                                name == variant && args.len() == values.len()
+                            })
+                            .ok_or_else(|| mismatch(&arg(), value))?;
+                        output.push(index as u8);
+                        for (arg, value) in variants[index].1.iter().zip(values) {
+                            self.encode(arg, value, output)?;
+                        }
+                    }
+                    (None, Value::Bytes(bytes)) => output.extend_from_slice(bytes),
+                    (None, _) => return Err(Error::TypeSizeUnavailable(name.to_string())),
+                    _ => return Err(mismatch(&arg(), value)),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn size<U: Default + Encode>() -> TypeDef {
+    TypeDef::Opaque(U::default().encode().len())
+}
+
+/// The definition of an unsigned integer type, by the size of its encoding.
+fn uint<U: Default + Encode>() -> TypeDef {
+    match U::default().encode().len() {
+        1 => TypeDef::Alias(named("u8")),
+        2 => TypeDef::Alias(named("u16")),
+        4 => TypeDef::Alias(named("u32")),
+        8 => TypeDef::Alias(named("u64")),
+        16 => TypeDef::Alias(named("u128")),
+        size => TypeDef::Opaque(size),
+    }
+}
+
+/// Creates the registry of a client, knowing the sizes of the system types of the runtime
+/// and the types it registers.
+pub(crate) fn runtime_registry<T: Runtime>(
+    types: Vec<(String, TypeDef)>,
+) -> TypeRegistry {
+    let mut registry = TypeRegistry::new();
+    registry.register("AccountId", size::<T::AccountId>());
+    registry.register("Hash", size::<T::Hash>());
+    registry.register("BlockNumber", uint::<T::BlockNumber>());
+    registry.register("Index", uint::<T::Index>());
+    T::register_types(&mut registry);
+    for (name, def) in types {
+        registry.register(&name, def);
+    }
+    registry
+}
+
+fn mismatch(arg: &EventArg, value: &Value) -> Error {
+    Error::Other(format!("Cannot encode {:?} as {:?}", value, arg))
+}
+
+fn builtin_size(name: &str) -> usize {
+    match &name[1..] {
+        "8" => 1,
+        "16" => 2,
+        "32" => 4,
+        "64" => 8,
+        _ => 16,
+    }
+}
+
+fn is_builtin(name: &str) -> bool {
+    matches!(
+        name,
+        "bool"
+            | "u8"
+            | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::Encode;
+    use sp_runtime::{
+        DispatchError,
+        DispatchResult,
+    };
+
+    fn decode<E: Encode>(ty: &str, value: E) -> Value {
+        let registry = TypeRegistry::new();
+        let arg: EventArg = ty.parse().unwrap();
+        let bytes = value.encode();
+        let mut input = &bytes[..];
+        let value = registry.decode(&arg, &mut input).unwrap();
+        assert!(input.is_empty(), "{} left bytes undecoded", ty);
+        value
+    }
+
+    #[test]
+    fn test_decode_values() {
+        assert_eq!(decode("u64", 5u64), Value::Uint(5));
+        assert_eq!(
+            decode("Compact<u128>", Compact(1u128 << 100)),
+            Value::Uint(1 << 100)
+        );
+        assert_eq!(
+            decode("Vec<Compact<u32>>", vec![Compact(1u32), Compact(1 << 20)]),
+            Value::Sequence(vec![Value::Uint(1), Value::Uint(1 << 20)])
+        );
+        assert_eq!(
+            decode("Option<(bool, i16)>", Some((true, -1i16))),
+            Value::Option(Some(Box::new(Value::Tuple(vec![
+                Value::Bool(true),
+                Value::Int(-1)
+            ]))))
+        );
+        assert_eq!(decode("[u8; 3]", [1u8, 2, 3]), Value::Bytes(vec![1, 2, 3]));
+        assert_eq!(
+            decode("Vec<Vec<u8>>", vec![vec![1u8], vec![]]),
+            Value::Sequence(vec![Value::Bytes(vec![1]), Value::Bytes(vec![])])
+        );
+        assert_eq!(
+            decode("Result<u8, u16>", Err::<u8, u16>(7)),
+            Value::Result(Err(Box::new(Value::Uint(7))))
+        );
+    }
+
+    #[test]
+    fn test_encode_values() {
+        let registry = TypeRegistry::new();
+        let encode = |ty: &str, value: Value| {
+            let mut bytes = Vec::new();
+            registry
+                .encode(&ty.parse().unwrap(), &value, &mut bytes)
+                .map(|_| bytes)
+        };
+        assert_eq!(encode("u16", Value::Uint(258)).unwrap(), 258u16.encode());
+        assert!(encode("u8", Value::Uint(256)).is_err());
+        assert_eq!(encode("i32", Value::Int(-2)).unwrap(), (-2i32).encode());
+        assert_eq!(
+            encode("Compact<T::Balance>", Value::Uint(1 << 40)).unwrap(),
+            Compact(1u64 << 40).encode()
+        );
+        assert_eq!(
+            encode(
+                "Vec<(u8, Option<bool>)>",
+                Value::Sequence(vec![Value::Tuple(vec![
+                    Value::Uint(1),
+                    Value::Option(Some(Box::new(Value::Bool(true))))
+                ])])
+            )
+            .unwrap(),
+            vec![(1u8, Some(true))].encode()
+        );
+        assert_eq!(
+            encode("Vec<u8>", Value::Bytes(vec![1, 2])).unwrap(),
+            vec![1u8, 2].encode()
+        );
+        assert_eq!(
+            encode("Pays", Value::Variant("No".into(), vec![])).unwrap(),
+            vec![1]
+        );
+        // unknown types are passed through as encoded bytes
+        assert_eq!(
+            encode(
+                "<T::Lookup as StaticLookup>::Source",
+                Value::Bytes(vec![7; 32])
+            )
+            .unwrap(),
+            vec![7; 32]
+        );
+        assert!(encode("Source", Value::Uint(1)).is_err());
+    }
+
+    #[test]
+    fn test_decode_truncated_sequence() {
+        let registry = TypeRegistry::new();
+        let mut bytes = Compact(u32::MAX).encode();
+        bytes.extend(&[1, 2, 3]);
+        for ty in &["Vec<u8>", "Vec<u32>", "Vec<Vec<u8>>"] {
+            let arg: EventArg = ty.parse().unwrap();
+            assert!(registry.decode(&arg, &mut &bytes[..]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decode_dispatch_result() {
+        let error = DispatchError::Module {
+            index: 1,
+            error: 2,
+            message: None,
+        };
+        assert_eq!(
+            decode("DispatchResult", DispatchResult::Err(error)),
+            Value::Result(Err(Box::new(Value::Variant(
+                "Module".into(),
+                vec![Value::Uint(1), Value::Uint(2)]
+            ))))
+        );
+    }
+}
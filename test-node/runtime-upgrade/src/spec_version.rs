@@ -0,0 +1,3 @@
+// Spec version of the upgraded test-node runtime, higher than the one of
+// `test-node-runtime` so the runtime can be upgraded to it.
+2
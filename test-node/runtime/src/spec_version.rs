@@ -0,0 +1,2 @@
+// Spec version of the test-node runtime, included in its `VERSION`.
+1
@@ -15,4 +15,5 @@
 // along with substrate-subxt.  If not, see <http://www.gnu.org/licenses/>.
 
 pub mod chain_spec;
+pub mod rpc;
 pub mod service;
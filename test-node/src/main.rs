@@ -18,6 +18,7 @@
 #![warn(missing_docs)]
 
 mod chain_spec;
+mod rpc;
 #[macro_use]
 mod service;
 mod cli;